@@ -0,0 +1,167 @@
+//! An accountability trail for the changes a client makes.
+//!
+//! If an [`AuditSink`] was registered with
+//! [`RustbloxClientBuilder::audit_sink`](crate::client::builder::RustbloxClientBuilder::audit_sink),
+//! every call that doesn't use `GET` is recorded as an [`AuditEntry`] once it finishes, whether
+//! it worked or not. That covers the mutating calls (kicks, rank changes, join request decisions,
+//! display name changes, ...), but also lookups Roblox happens to take as a `POST`, like
+//! [`get_user_presences`](RustbloxClient::get_user_presences) or OAuth token exchanges.
+//! [`JsonLinesAuditSink`] writes them to a file, one JSON object per line.
+
+use crate::client::RustbloxClient;
+use std::path::PathBuf;
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// How a recorded call turned out.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "status", content = "error", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum AuditOutcome {
+    /// Roblox accepted the call.
+    Succeeded,
+    /// The call failed. Contains the error.
+    Failed(String),
+    /// The client is in dry-run mode, so the call wasn't sent. Read-only calls are
+    /// still sent in dry-run mode, so this is only used for mutating ones.
+    DryRun,
+}
+
+/// A record of one call that doesn't use `GET`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    /// When the call finished, as a Unix timestamp (in seconds).
+    pub timestamp: u64,
+    /// The ID of the account the call was made as, if it could be determined.
+    pub actor_id: Option<usize>,
+    /// What the call did, named after its [`Endpoint`](crate::endpoint::Endpoint) (i.e. `KickUser`).
+    pub action: String,
+    /// What the call acted on (i.e. `{ "group_id": 1, "user_id": 2 }`).
+    pub targets: serde_json::Map<String, serde_json::Value>,
+    /// The state of the target before the call, where it is known (i.e. the user's old role).
+    pub previous_state: Option<serde_json::Value>,
+    /// How the call turned out.
+    pub outcome: AuditOutcome,
+}
+
+/// Somewhere to keep [`AuditEntry`]s.
+#[async_trait::async_trait]
+pub trait AuditSink: Send + Sync {
+    /// Records `entry`.
+    ///
+    /// # Errors
+    ///
+    /// Errors are logged, but don't affect the call that is being recorded.
+    async fn record(
+        &self,
+        entry: &AuditEntry,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// An [`AuditSink`] that appends entries to a file as JSON Lines (one JSON object per line).
+#[derive(Debug)]
+pub struct JsonLinesAuditSink {
+    path: PathBuf,
+    // Keeps concurrent calls from interleaving their lines
+    lock: Mutex<()>,
+}
+
+impl JsonLinesAuditSink {
+    /// Creates a sink that appends to the file at `path`. The file is created if it
+    /// doesn't exist.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditSink for JsonLinesAuditSink {
+    async fn record(
+        &self,
+        entry: &AuditEntry,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+
+        let _guard = self.lock.lock().await;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+impl RustbloxClient {
+    /// Returns whether or not this [`RustbloxClient`] records its calls (see [`audit`](crate::audit)).
+    #[must_use]
+    pub fn is_audited(&self) -> bool {
+        self.audit_sink.is_some()
+    }
+
+    /// Returns the ID of the account this client acts as, for audit entries. The ID is
+    /// looked up once per cookie and remembered.
+    #[async_recursion::async_recursion]
+    pub(crate) async fn audit_actor_id(&self) -> Option<usize> {
+        let cookie = self.roblox_cookie()?;
+        if let Some(id) = self
+            .actor_ids
+            .lock()
+            .ok()
+            .and_then(|ids| ids.get(&cookie).copied())
+        {
+            return Some(id);
+        }
+
+        let id = match self.get_authenticated_user().await {
+            Ok(user) => user.id,
+            Err(why) => {
+                warn!("Couldn't find out who the acting account is for the audit journal:\n{why}");
+                return None;
+            }
+        };
+        if let Ok(mut ids) = self.actor_ids.lock() {
+            ids.insert(cookie, id);
+        }
+        Some(id)
+    }
+
+    /// Returns `user_id`'s current role in `group_id`, for audit entries. Nothing is looked
+    /// up unless the client is audited.
+    #[async_recursion::async_recursion]
+    pub(crate) async fn audit_previous_role(
+        &self,
+        group_id: usize,
+        user_id: usize,
+    ) -> Option<serde_json::Value> {
+        if !self.is_audited() {
+            return None;
+        }
+
+        let groups = self.get_user_group_roles(user_id).await.ok()?;
+        let role = groups
+            .data
+            .into_iter()
+            .find(|group| group.group.id == group_id)
+            .map(|group| group.role);
+        // Not being in the group is a previous state too
+        Some(serde_json::json!({ "role": role }))
+    }
+
+    /// Sends an audit entry to the sink, if there is one.
+    pub(crate) async fn record_audit_entry(&self, entry: AuditEntry) {
+        if let Some(sink) = &self.audit_sink {
+            if let Err(why) = sink.record(&entry).await {
+                warn!("Couldn't record an audit entry:\n{why}\nEntry: {entry:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Support for Roblox's generic challenge system.
+//!
+//! Sensitive endpoints can answer with a set of `rblx-challenge-*` headers instead of
+//! succeeding. Rustblox turns those into a [`Challenge`] and, if a [`ChallengeSolver`]
+//! was registered with
+//! [`RustbloxClientBuilder::challenge_solver`](crate::client::builder::RustbloxClientBuilder::challenge_solver),
+//! hands it over to be solved. Once solved, the client tells Roblox to continue and resends
+//! the original request with the solution attached. Without a solver (or if the solver
+//! passes on a challenge), the request fails with
+//! [`RequestError::ChallengeRequired`](crate::error::RequestError::ChallengeRequired).
+//!
+//! The continue call is made with the same credentials as the challenged request, and like
+//! any other call that doesn't use `GET`, it is recorded in the [audit journal](crate::audit)
+//! (as `ContinueChallenge`).
+
+use crate::client::{AuthMethod, RustbloxClient};
+use crate::endpoint::Endpoint;
+use crate::error::RequestError;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::Method;
+
+/// Where solved challenges are reported before the original request is retried.
+pub(crate) const CHALLENGE_CONTINUE_URL: &str = "https://apis.roblox.com/challenge/v1/continue";
+
+pub(crate) const CHALLENGE_ID_HEADER: &str = "rblx-challenge-id";
+pub(crate) const CHALLENGE_TYPE_HEADER: &str = "rblx-challenge-type";
+pub(crate) const CHALLENGE_METADATA_HEADER: &str = "rblx-challenge-metadata";
+
+/// The kind of challenge Roblox wants solved, from the `rblx-challenge-type` header.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ChallengeType {
+    /// Two-step verification (i.e. an authenticator app or email code).
+    TwoStepVerification,
+    /// A captcha.
+    Captcha,
+    /// Re-entering the account's password.
+    Reauthentication,
+    /// Setting up an authenticator app before continuing.
+    ForceAuthenticator,
+    /// A proof-of-work puzzle.
+    ProofOfWork,
+    /// A challenge type that Rustblox doesn't know about. Contains the header value.
+    Other(String),
+}
+
+impl ChallengeType {
+    /// Returns the value Roblox uses for this challenge type in headers and request bodies.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::TwoStepVerification => "twostepverification",
+            Self::Captcha => "captcha",
+            Self::Reauthentication => "reauthentication",
+            Self::ForceAuthenticator => "forceauthenticator",
+            Self::ProofOfWork => "proofofwork",
+            Self::Other(other) => other.as_str(),
+        }
+    }
+}
+
+impl From<&str> for ChallengeType {
+    fn from(value: &str) -> Self {
+        match value {
+            "twostepverification" => Self::TwoStepVerification,
+            "captcha" => Self::Captcha,
+            "reauthentication" => Self::Reauthentication,
+            "forceauthenticator" => Self::ForceAuthenticator,
+            "proofofwork" => Self::ProofOfWork,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+/// A challenge that Roblox wants solved before it will accept a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    /// The ID of the challenge, from the `rblx-challenge-id` header.
+    pub id: String,
+    /// The kind of challenge, from the `rblx-challenge-type` header.
+    pub challenge_type: ChallengeType,
+    /// The base64-encoded JSON metadata, from the `rblx-challenge-metadata` header.
+    pub metadata: String,
+}
+
+impl Challenge {
+    /// Reads a challenge out of a set of response headers. Returns `None` if
+    /// the headers don't contain a challenge.
+    #[must_use]
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from)
+        };
+
+        Some(Self {
+            id: header(CHALLENGE_ID_HEADER)?,
+            challenge_type: ChallengeType::from(header(CHALLENGE_TYPE_HEADER)?.as_str()),
+            metadata: header(CHALLENGE_METADATA_HEADER).unwrap_or_default(),
+        })
+    }
+
+    /// Decodes the challenge's metadata. Returns `None` if it isn't valid
+    /// base64-encoded JSON.
+    #[must_use]
+    pub fn metadata_json(&self) -> Option<serde_json::Value> {
+        let decoded = STANDARD.decode(&self.metadata).ok()?;
+        serde_json::from_slice(&decoded).ok()
+    }
+}
+
+/// The answer to a [`Challenge`], sent back to Roblox when the original request is retried.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChallengeSolution {
+    /// The ID of the challenge that was solved.
+    pub challenge_id: String,
+    /// The kind of challenge that was solved.
+    pub challenge_type: ChallengeType,
+    /// The continuation metadata, as JSON. What goes in here depends on the challenge type.
+    pub metadata: serde_json::Value,
+}
+
+impl ChallengeSolution {
+    /// Creates a solution to `challenge` with the given continuation metadata.
+    #[must_use]
+    pub fn new(challenge: &Challenge, metadata: serde_json::Value) -> Self {
+        Self {
+            challenge_id: challenge.id.clone(),
+            challenge_type: challenge.challenge_type.clone(),
+            metadata,
+        }
+    }
+
+    /// Returns the headers that are attached to the retried request.
+    pub(crate) fn headers(&self) -> Result<HeaderMap, String> {
+        let encoded = STANDARD.encode(self.metadata.to_string());
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            (CHALLENGE_ID_HEADER, self.challenge_id.as_str()),
+            (CHALLENGE_TYPE_HEADER, self.challenge_type.as_str()),
+            (CHALLENGE_METADATA_HEADER, encoded.as_str()),
+        ] {
+            let value = HeaderValue::from_str(value).map_err(|e| e.to_string())?;
+            headers.insert(name, value);
+        }
+        Ok(headers)
+    }
+}
+
+/// Solves challenges on behalf of a [`RustbloxClient`].
+///
+/// The client is passed in so that solvers can make requests of their own
+/// (i.e. to submit a verification code).
+#[async_trait::async_trait]
+pub trait ChallengeSolver: Send + Sync {
+    /// Attempts to solve `challenge`.
+    ///
+    /// Return `Ok(None)` if this solver doesn't handle this kind of challenge. The
+    /// original request then fails with
+    /// [`RequestError::ChallengeRequired`](crate::error::RequestError::ChallengeRequired).
+    ///
+    /// # Errors
+    ///
+    /// Any error returned here is returned from the original request.
+    async fn solve(
+        &self,
+        client: &RustbloxClient,
+        challenge: &Challenge,
+    ) -> Result<Option<ChallengeSolution>, RequestError>;
+}
+
+/// Tells Roblox that a challenge was solved. Sent with the challenged request's credentials.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ContinueChallenge {
+    challenge_id: String,
+    challenge_type: String,
+    challenge_metadata: String,
+    #[serde(skip)]
+    auth: AuthMethod,
+    #[serde(skip)]
+    url: String,
+}
+
+impl Endpoint for ContinueChallenge {
+    type Body = Self;
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        self.auth
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    // Only unblocks the original request, which decides for itself
+    fn mutating(&self) -> bool {
+        false
+    }
+
+    fn audit_targets(&self) -> Vec<(&'static str, serde_json::Value)> {
+        vec![
+            ("challenge_id", self.challenge_id.clone().into()),
+            ("challenge_type", self.challenge_type.clone().into()),
+        ]
+    }
+}
+
+impl RustbloxClient {
+    /// Tells Roblox that a challenge was solved, so that the original request
+    /// can be retried. `auth` is how the original request was authenticated.
+    pub(crate) async fn continue_challenge(
+        &self,
+        solution: &ChallengeSolution,
+        auth: AuthMethod,
+    ) -> Result<(), RequestError> {
+        self.execute(ContinueChallenge {
+            challenge_id: solution.challenge_id.clone(),
+            challenge_type: solution.challenge_type.as_str().to_string(),
+            challenge_metadata: solution.metadata.to_string(),
+            auth,
+            url: self.challenge_continue_url.clone(),
+        })
+        .await?;
+
+        Ok(())
+    }
+}
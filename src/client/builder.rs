@@ -1,23 +1,70 @@
-use std::sync::{Arc, RwLock};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
 
-use crate::client::RustbloxClient;
+use crate::audit::AuditSink;
+use crate::challenge::{ChallengeSolver, CHALLENGE_CONTINUE_URL};
+use crate::credentials::{AuthEvent, CredentialProvider};
+use crate::drift::{DriftCallback, SchemaDrift};
+use crate::oauth::OAuthSession;
+use crate::twostepverification::{Totp, TwoStepVerificationSolver};
+
+use crate::client::{format_cookie, AuthCallback, RustbloxClient, CSRF_URL};
 use crate::error::ClientError;
 
 /// Builds a Rustblox client.
 ///
 /// # Fields
 /// - `reqwest_builder`: A holder for the RustbloxClient's internal
-/// reqwest client. Does not ever need to be set by the user.
+///   reqwest client. Does not ever need to be set by the user.
 /// - `roblox_cookie`: A .ROBLOSECURITY cookie to be used to authenticate
-/// certain requests. Does not need to be set if the user will not use authenticated
-/// endpoints (which are labeled). Can be set by [`insert_cookie`](RustbloxClientBuilder::insert_cookie).
+///   certain requests. Does not need to be set if the user will not use authenticated
+///   endpoints (which are labeled). Can be set by [`insert_cookie`](RustbloxClientBuilder::insert_cookie).
+/// - `api_key`: An Open Cloud API key to be used to authenticate requests to Open Cloud
+///   endpoints. Does not need to be set if the user will not use those endpoints.
+///   Can be set by [`api_key`](RustbloxClientBuilder::api_key).
+/// - `oauth`: An OAuth session to be used to authenticate requests on behalf of a user that
+///   authorized an OAuth 2.0 app. Does not need to be set if the user will not use those endpoints.
+///   Can be set by [`oauth`](RustbloxClientBuilder::oauth).
 /// - `auto_reauth`: Controls whether the Rustblox client built by this will attempt to
-/// automatically refresh its `x-csrf-token`. True by default. Can be manually overridden by
-/// [`automatic_reauthentication`](RustbloxClientBuilder::automatic_reauthentication).
+///   automatically refresh its `x-csrf-token`. True by default. Can be manually overridden by
+///   [`automatic_reauthentication`](RustbloxClientBuilder::automatic_reauthentication).
+/// - `validate_names`: Controls whether the Rustblox client built by this will check usernames
+///   and display names locally before sending them to Roblox. True by default. Can be manually
+///   overridden by [`client_side_validation`](RustbloxClientBuilder::client_side_validation).
+/// - `lazy_csrf`: Controls whether the Rustblox client built by this will wait to get an
+///   `x-csrf-token` until a request needs one. False by default. Can be manually overridden by
+///   [`lazy_csrf`](RustbloxClientBuilder::lazy_csrf).
+/// - `dry_run`: Controls whether the Rustblox client built by this skips mutating requests, logging
+///   them instead. False by default. Can be manually overridden by [`dry_run`](RustbloxClientBuilder::dry_run).
+/// - `challenge_solver`: Solves `rblx-challenge` challenges for the Rustblox client built by this.
+///   None by default. Can be set by [`challenge_solver`](RustbloxClientBuilder::challenge_solver).
+/// - `credential_provider`: Hands the Rustblox client built by this a fresh `.ROBLOSECURITY` cookie
+///   when Roblox rejects the current one. None by default. Can be set by
+///   [`credential_provider`](RustbloxClientBuilder::credential_provider).
+/// - `auth_callback`: Called with every [`AuthEvent`] the Rustblox client built by this goes through.
+///   None by default. Can be set by [`on_auth_event`](RustbloxClientBuilder::on_auth_event).
+/// - `audit_sink`: Records every call the Rustblox client built by this makes, except `GET` requests.
+///   None by default. Can be set by [`audit_sink`](RustbloxClientBuilder::audit_sink).
+/// - `detect_drift`: Controls whether the Rustblox client built by this compares responses with
+///   the types they are parsed into. False by default. Can be manually overridden by
+///   [`detect_schema_drift`](RustbloxClientBuilder::detect_schema_drift).
+/// - `drift_callback`: Called with every [`SchemaDrift`] the Rustblox client built by this finds.
+///   None by default. Can be set by [`on_schema_drift`](RustbloxClientBuilder::on_schema_drift).
 pub struct RustbloxClientBuilder {
     reqwest_builder: reqwest::ClientBuilder,
     roblox_cookie: Option<String>,
+    api_key: Option<String>,
+    oauth: Option<OAuthSession>,
     auto_reauth: bool,
+    validate_names: bool,
+    lazy_csrf: bool,
+    dry_run: bool,
+    challenge_solver: Option<Arc<dyn ChallengeSolver>>,
+    credential_provider: Option<Arc<dyn CredentialProvider>>,
+    auth_callback: Option<AuthCallback>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    detect_drift: bool,
+    drift_callback: Option<DriftCallback>,
 }
 
 impl Default for RustbloxClientBuilder {
@@ -32,6 +79,35 @@ fn get_user_agent() -> String {
 }
 
 impl RustbloxClientBuilder {
+    /// Inserts an Open Cloud API key into a `RustbloxClientBuilder`. It is sent in the
+    /// `x-api-key` header of requests that use [`AuthMethod::ApiKey`](crate::client::AuthMethod::ApiKey).
+    /// A client can have both an API key and a `.ROBLOSECURITY` cookie; each endpoint
+    /// picks the one it needs.
+    ///
+    /// # Errors
+    ///
+    /// This function cannot error.
+    #[inline]
+    pub fn api_key(mut self, api_key: &str) -> Self {
+        self.api_key = Some(api_key.to_string());
+        self
+    }
+
+    /// Sets the [`AuditSink`] that the client's calls (i.e. [`kick_user`](RustbloxClient::kick_user))
+    /// are recorded in. Every call except `GET` requests is recorded. See [`audit`](crate::audit).
+    ///
+    /// Where it is known, the state of the target before the call is recorded too, which
+    /// costs an extra read-only request for some calls.
+    ///
+    /// # Errors
+    ///
+    /// This function cannot error.
+    #[inline]
+    pub fn audit_sink(mut self, sink: impl AuditSink + 'static) -> Self {
+        self.audit_sink = Some(Arc::new(sink));
+        self
+    }
+
     /// Sets whether or not the client should automatically reauthenticate itself if the Roblox
     /// API returns a 403 status code.
     /// The RustbloxClient will only attempt reauthentication once (see the definition of insanity
@@ -59,29 +135,133 @@ impl RustbloxClientBuilder {
 
         Ok(RustbloxClient {
             reqwest_client: built_client,
-            roblox_cookie: self.roblox_cookie,
-            csrf_token: Arc::new(RwLock::new(None)),
+            roblox_cookie: Arc::new(RwLock::new(self.roblox_cookie)),
+            api_key: self.api_key,
+            oauth: self.oauth,
+            csrf_token: Arc::new(tokio::sync::RwLock::new(None)),
             auto_reauth: self.auto_reauth,
+            validate_names: self.validate_names,
+            lazy_csrf: self.lazy_csrf,
+            dry_run: self.dry_run,
+            challenge_solver: self.challenge_solver,
+            credential_provider: self.credential_provider,
+            auth_callback: self.auth_callback,
+            audit_sink: self.audit_sink,
+            detect_drift: self.detect_drift,
+            drift_callback: self.drift_callback,
+            actor_ids: Arc::new(Mutex::new(HashMap::new())),
+            csrf_url: CSRF_URL.to_string(),
+            challenge_continue_url: CHALLENGE_CONTINUE_URL.to_string(),
         })
     }
 
-    /// Inserts a token into a `RustbloxClientBuilder`.
+    /// Sets whether or not the client should wait to get an `x-csrf-token` until a request
+    /// needs one. When enabled, [`login`](RustbloxClient::login) only checks the
+    /// `.ROBLOSECURITY` cookie, and the first write request picks up its token from the
+    /// 403 response Roblox sends back.
     ///
     /// # Errors
     ///
-    /// This function returns an error if the cookie provided is invalid.
-    pub fn insert_cookie(mut self, cookie: &str) -> Result<Self, ClientError> {
-        // All .ROBLOSECURITY cookies should start with this
-        // unless Roblox just decides to randomly change it some day
-        // ...which they might
+    /// This function cannot error.
+    #[inline]
+    pub fn lazy_csrf(mut self, lazy_csrf: bool) -> Self {
+        self.lazy_csrf = lazy_csrf;
+        self
+    }
 
-        (!(cookie.starts_with("_|WARNING")))
-            .then_some(ClientError::InvalidCookie)
-            .map_or(Ok(()), Err)?;
+    /// Sets the [`ChallengeSolver`] the client hands `rblx-challenge` challenges to.
+    /// Solved challenges are continued and the original request is resent once.
+    ///
+    /// # Errors
+    ///
+    /// This function cannot error.
+    #[inline]
+    pub fn challenge_solver(mut self, solver: impl ChallengeSolver + 'static) -> Self {
+        self.challenge_solver = Some(Arc::new(solver));
+        self
+    }
 
-        let formatted_cookie = format!(".ROBLOSECURITY={cookie}");
+    /// Sets up the client to complete two-step verification challenges on its own, using codes
+    /// generated from the account's base32-encoded authenticator `secret`. This registers a
+    /// [`TwoStepVerificationSolver`] as the client's [`ChallengeSolver`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the secret is invalid.
+    pub fn totp_secret(self, secret: &str) -> Result<Self, ClientError> {
+        let totp = Totp::from_base32(secret)?;
+        Ok(self.challenge_solver(TwoStepVerificationSolver::new(totp)))
+    }
 
-        self.roblox_cookie = Some(formatted_cookie);
+    /// Sets whether or not the client should check usernames and display names against
+    /// Roblox's rules (see [`validation`](crate::validation)) before sending them.
+    /// Names that fail the check are rejected with a [`RequestError::InvalidName`](crate::error::RequestError::InvalidName)
+    /// without contacting Roblox.
+    ///
+    /// # Errors
+    ///
+    /// This function cannot error.
+    #[inline]
+    pub fn client_side_validation(mut self, validate_names: bool) -> Self {
+        self.validate_names = validate_names;
+        self
+    }
+
+    /// Sets the [`CredentialProvider`] the client asks for a fresh `.ROBLOSECURITY` cookie when
+    /// Roblox rejects the current one. The original request is then resent once with the
+    /// new cookie.
+    ///
+    /// # Errors
+    ///
+    /// This function cannot error.
+    #[inline]
+    pub fn credential_provider(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.credential_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Sets whether or not the client should check responses for
+    /// [schema drift](crate::drift). When enabled, each response to an
+    /// [`Endpoint`](crate::endpoint::Endpoint) is compared with the type it is parsed into,
+    /// and unknown or missing fields are logged at the `warn` level (and handed to the
+    /// [`on_schema_drift`](RustbloxClientBuilder::on_schema_drift) callback, if there is one).
+    /// This parses every response twice, so it is best left for development and monitoring.
+    ///
+    /// # Errors
+    ///
+    /// This function cannot error.
+    #[inline]
+    pub fn detect_schema_drift(mut self, detect_drift: bool) -> Self {
+        self.detect_drift = detect_drift;
+        self
+    }
+
+    /// Sets whether or not the client should run in dry-run mode. In dry-run mode, mutating
+    /// calls (i.e. [`kick_user`](RustbloxClient::kick_user)) aren't sent. Instead, the method
+    /// and URL of the request that would have been sent are logged at the `info` level (along
+    /// with the endpoint and what it acts on, but never the body, which can hold passwords)
+    /// and a successful response is made up. Read-only calls are sent as usual.
+    ///
+    /// The made-up response is `null`, which every built-in mutating call accepts. Your own
+    /// mutating [`Endpoint`](crate::endpoint::Endpoint)s need a `Response` that can be parsed
+    /// from `null` too (see [`Endpoint::Response`](crate::endpoint::Endpoint::Response)).
+    ///
+    /// # Errors
+    ///
+    /// This function cannot error.
+    #[inline]
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Inserts a token into a `RustbloxClientBuilder`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the cookie provided is invalid.
+    pub fn insert_cookie(mut self, cookie: &str) -> Result<Self, ClientError> {
+        self.roblox_cookie = Some(format_cookie(cookie)?);
 
         Ok(self)
     }
@@ -92,7 +272,60 @@ impl RustbloxClientBuilder {
         Self {
             reqwest_builder: reqwest::ClientBuilder::new().user_agent(get_user_agent()),
             roblox_cookie: None,
+            api_key: None,
+            oauth: None,
             auto_reauth: true,
+            validate_names: true,
+            lazy_csrf: false,
+            dry_run: false,
+            challenge_solver: None,
+            credential_provider: None,
+            auth_callback: None,
+            audit_sink: None,
+            detect_drift: false,
+            drift_callback: None,
         }
     }
+
+    /// Inserts an OAuth session into a `RustbloxClientBuilder`. Its access token is sent in
+    /// the `Authorization` header of requests that use
+    /// [`AuthMethod::OAuth`](crate::client::AuthMethod::OAuth), and is refreshed whenever
+    /// it is about to expire or Roblox rejects it.
+    ///
+    /// # Errors
+    ///
+    /// This function cannot error.
+    #[inline]
+    pub fn oauth(mut self, session: OAuthSession) -> Self {
+        self.oauth = Some(session);
+        self
+    }
+
+    /// Sets a function that is called with every [`AuthEvent`] the client goes through
+    /// (i.e. to alert someone when the cookie expires).
+    ///
+    /// # Errors
+    ///
+    /// This function cannot error.
+    #[inline]
+    pub fn on_auth_event(mut self, callback: impl Fn(AuthEvent) + Send + Sync + 'static) -> Self {
+        self.auth_callback = Some(Arc::new(callback));
+        self
+    }
+
+    /// Sets a function that is called with every [`SchemaDrift`] the client finds. This also
+    /// enables [`detect_schema_drift`](RustbloxClientBuilder::detect_schema_drift).
+    ///
+    /// # Errors
+    ///
+    /// This function cannot error.
+    #[inline]
+    pub fn on_schema_drift(
+        mut self,
+        callback: impl Fn(&SchemaDrift) + Send + Sync + 'static,
+    ) -> Self {
+        self.detect_drift = true;
+        self.drift_callback = Some(Arc::new(callback));
+        self
+    }
 }
@@ -1,5 +1,12 @@
 pub mod builder;
+mod request;
 mod rustblox_client;
+mod session;
 
-pub(crate) use rustblox_client::RequestComponents;
-pub use rustblox_client::RustbloxClient;
+pub use request::RustbloxRequest;
+pub use reqwest::Method;
+pub(crate) use rustblox_client::{
+    format_cookie, AuthCallback, DriftCheck, Recoveries, RequestComponents, CSRF_URL,
+};
+pub use rustblox_client::{AuthMethod, RustbloxClient};
+pub use session::AccountSession;
@@ -0,0 +1,224 @@
+use crate::client::{AuthMethod, DriftCheck, Recoveries, RequestComponents, RustbloxClient};
+use crate::error::RequestError;
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::{Method, Url};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+
+/// A request to an arbitrary Roblox API endpoint. Created by
+/// [`request`](RustbloxClient::request) or [`request_json`](RustbloxClient::request_json).
+///
+/// This is meant for endpoints that Rustblox doesn't wrap yet. Requests made
+/// this way go through the same machinery as every other function on the
+/// [`RustbloxClient`], so the `.ROBLOSECURITY` cookie, `x-csrf-token` and
+/// automatic reauthentication are all handled for you.
+///
+/// ```no_run
+/// # async fn example(client: rustblox::client::RustbloxClient) {
+/// use rustblox::client::Method;
+///
+/// let description = client
+///     .request_json(Method::POST, "https://users.roblox.com/v1/description")
+///     .authenticated(true)
+///     .json(&serde_json::json!({ "description": "Hello!" }))
+///     .send()
+///     .await;
+/// # }
+/// ```
+#[must_use = "requests do nothing until they are sent"]
+pub struct RustbloxRequest<'a, T> {
+    client: &'a RustbloxClient,
+    method: Method,
+    url: String,
+    query: Vec<(String, String)>,
+    body: Option<Result<String, String>>,
+    content_type: &'static str,
+    auth: AuthMethod,
+    mutating: bool,
+    drift_check: Option<DriftCheck>,
+    action: Option<String>,
+    permission_error_codes: Vec<i16>,
+    response: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> RustbloxRequest<'a, T>
+where
+    T: DeserializeOwned,
+{
+    pub(crate) fn new(client: &'a RustbloxClient, method: Method, url: String) -> Self {
+        Self {
+            client,
+            url,
+            query: Vec::new(),
+            body: None,
+            content_type: "application/json",
+            auth: AuthMethod::None,
+            mutating: !method.is_safe(),
+            drift_check: None,
+            action: None,
+            permission_error_codes: Vec::new(),
+            method,
+            response: PhantomData,
+        }
+    }
+
+    /// Sets whether or not this request needs the `.ROBLOSECURITY` cookie
+    /// and `x-csrf-token`. False by default. Shorthand for [`auth`](RustbloxRequest::auth).
+    #[inline]
+    pub fn authenticated(self, needs_auth: bool) -> Self {
+        self.auth(if needs_auth {
+            AuthMethod::Cookie
+        } else {
+            AuthMethod::None
+        })
+    }
+
+    /// Sets the credentials this request is sent with. [`AuthMethod::None`] by default.
+    #[inline]
+    pub fn auth(mut self, auth: AuthMethod) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Appends a query parameter to the request URL. The value is percent-encoded.
+    pub fn query(mut self, key: &str, value: impl ToString) -> Self {
+        self.query.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets whether or not this request changes anything on Roblox's end. Mutating requests
+    /// are skipped in [dry-run mode](crate::client::builder::RustbloxClientBuilder::dry_run).
+    /// By default, every request that isn't a `GET`, `HEAD`, `OPTIONS` or `TRACE` is
+    /// considered mutating.
+    #[inline]
+    pub fn mutating(mut self, mutating: bool) -> Self {
+        self.mutating = mutating;
+        self
+    }
+
+    /// Sets the error codes that mean the account lacks a permission when the endpoint
+    /// answers with a `403`. Those become [`RequestError::InsufficientPermissions`]; every
+    /// other `403` is [`RequestError::Forbidden`]. None by default.
+    pub fn permission_error_codes(mut self, codes: &[i16]) -> Self {
+        self.permission_error_codes = codes.to_vec();
+        self
+    }
+
+    /// Compares the response with `T` when
+    /// [schema drift detection](crate::client::builder::RustbloxClientBuilder::detect_schema_drift)
+    /// is enabled.
+    #[inline]
+    pub(crate) fn drift_checked(mut self) -> Self
+    where
+        T: Serialize,
+    {
+        self.drift_check = Some(crate::drift::check::<T>);
+        self
+    }
+
+    /// Describes what the request does (i.e. `KickUser {"group_id":1}`), for logs that
+    /// leave the body out.
+    #[inline]
+    pub(crate) fn action(mut self, action: String) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    /// Sets the JSON body of the request. The `Content-Type` and `Content-Length`
+    /// headers are set for you.
+    pub fn json<B>(mut self, body: &B) -> Self
+    where
+        B: Serialize + ?Sized,
+    {
+        self.body = Some(serde_json::to_string(body).map_err(|e| e.to_string()));
+        self.content_type = "application/json";
+        self
+    }
+
+    /// Sets the form-encoded body of the request. The `Content-Type` and `Content-Length`
+    /// headers are set for you.
+    pub fn form<B>(mut self, body: &B) -> Self
+    where
+        B: Serialize + ?Sized,
+    {
+        self.body = Some(serde_urlencoded::to_string(body).map_err(|e| e.to_string()));
+        self.content_type = "application/x-www-form-urlencoded";
+        self
+    }
+
+    /// Sends the request and parses the response body as `T`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The URL is invalid or the body could not be serialized.
+    /// - The request needs credentials (a `.ROBLOSECURITY` cookie or an API key) that you do not have set.
+    /// - The endpoint responds with an error.
+    /// - The response body could not be parsed as `T`.
+    pub async fn send(self) -> Result<T, RequestError> {
+        let mut url = Url::parse(&self.url).map_err(|e| {
+            RequestError::RequestError(self.url.clone(), format!("Invalid URL:\n{e}"))
+        })?;
+        if !self.query.is_empty() {
+            url.query_pairs_mut().extend_pairs(&self.query);
+        }
+
+        let body = self.body.transpose().map_err(|e| {
+            RequestError::RequestError(
+                self.url.clone(),
+                format!("Couldn't serialize request body:\n{e}"),
+            )
+        })?;
+
+        let mut headers = HeaderMap::new();
+        match &body {
+            Some(body) => {
+                headers.insert("Content-Length", HeaderValue::from(body.len()));
+                headers.insert("Content-Type", HeaderValue::from_static(self.content_type));
+            }
+            // Roblox wants an explicit length on bodiless writes
+            None if self.method != Method::GET => {
+                headers.insert("Content-Length", HeaderValue::from(0));
+            }
+            None => {}
+        }
+
+        let components = RequestComponents {
+            auth: self.auth,
+            method: self.method,
+            url: url.to_string(),
+            headers: Some(headers),
+            body,
+            mutating: self.mutating,
+            drift_check: self.drift_check,
+            action: self.action,
+            permission_error_codes: self.permission_error_codes,
+        };
+
+        self.client
+            .make_request::<T>(components, Recoveries::default())
+            .await
+    }
+}
+
+impl RustbloxClient {
+    /// Starts building a request to an arbitrary Roblox API endpoint, with
+    /// the response parsed as `T`. See [`RustbloxRequest`].
+    pub fn request<T>(&self, method: Method, url: impl Into<String>) -> RustbloxRequest<'_, T>
+    where
+        T: DeserializeOwned,
+    {
+        RustbloxRequest::new(self, method, url.into())
+    }
+
+    /// Starts building a request to an arbitrary Roblox API endpoint, with
+    /// the response left as untyped JSON. See [`RustbloxRequest`].
+    pub fn request_json(
+        &self,
+        method: Method,
+        url: impl Into<String>,
+    ) -> RustbloxRequest<'_, serde_json::Value> {
+        self.request::<serde_json::Value>(method, url)
+    }
+}
@@ -1,24 +1,107 @@
-use crate::error::{ClientError, RequestError, RobloxApiError, RobloxApiErrors};
-use reqwest::header::HeaderMap;
+use crate::audit::AuditSink;
+use crate::challenge::{Challenge, ChallengeSolver, CHALLENGE_ID_HEADER};
+use crate::credentials::{AuthEvent, CredentialProvider};
+use crate::drift::{DriftCallback, SchemaDrift};
+use crate::error::{ClientError, DeserializeError, HttpError, RequestError, RobloxApiErrors};
+use crate::oauth::OAuthSession;
+use reqwest::header::{HeaderMap, SET_COOKIE};
 use reqwest::Method;
 use serde::de::DeserializeOwned;
-use std::sync::{Arc, RwLock};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Where `x-csrf-token`s are fetched from. The request has no side effects, even if
+/// Roblox were to accept it.
+pub(crate) const CSRF_URL: &str = "https://catalog.roblox.com/v1/catalog/items/details";
+
+/// The credentials a request is sent with.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AuthMethod {
+    /// The request is sent without any credentials.
+    #[default]
+    None,
+    /// The request is sent with the `.ROBLOSECURITY` cookie and `x-csrf-token`.
+    Cookie,
+    /// The request is sent with the Open Cloud API key in the `x-api-key` header.
+    ApiKey,
+    /// The request is sent with the OAuth session's access token in the `Authorization` header.
+    OAuth,
+}
+
+/// The ways a request has already recovered from a failed attempt. Each one is only
+/// tried once, but they're tracked apart so that one doesn't use up the other (i.e. a
+/// write request sent with a refreshed cookie still needs the CSRF handshake).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Recoveries {
+    /// The `.ROBLOSECURITY` cookie or OAuth access token was replaced after a 401.
+    pub(crate) credentials: bool,
+    /// The `x-csrf-token` was replaced after a 403.
+    pub(crate) csrf: bool,
+}
+
+/// Called with every [`AuthEvent`] a client goes through.
+pub(crate) type AuthCallback = Arc<dyn Fn(AuthEvent) + Send + Sync>;
 
 pub(crate) struct RequestComponents {
-    pub(crate) needs_auth: bool,
+    pub(crate) auth: AuthMethod,
     pub(crate) method: Method,
     pub(crate) url: String,
     pub(crate) headers: Option<HeaderMap>,
     pub(crate) body: Option<String>,
+    pub(crate) mutating: bool,
+    // Compares a response body with the type it's parsed into. Only set for endpoints,
+    // since their response types can be serialized back for comparison
+    pub(crate) drift_check: Option<DriftCheck>,
+    // What an endpoint request does (i.e. `KickUser {"group_id":1}`), for logs that
+    // can't include the body. Only set for endpoints
+    pub(crate) action: Option<String>,
+    // Which 403 error codes mean the account lacks a permission
+    pub(crate) permission_error_codes: Vec<i16>,
+}
+
+impl RequestComponents {
+    /// Describes the request for the dry-run log. The body is left out, since it can
+    /// hold passwords and OAuth secrets.
+    pub(crate) fn dry_run_summary(&self) -> String {
+        match &self.action {
+            Some(action) => format!("{} {} ({action})", self.method, self.url),
+            None => format!("{} {}", self.method, self.url),
+        }
+    }
 }
 
+pub(crate) type DriftCheck = fn(&str, &str) -> Option<SchemaDrift>;
+
 /// The Rustblox client. All functions necessary to contact endpoints
 /// are contained within `impl`s. Eventually, users will be able to control
 /// which functions they want with crate features.
+///
+/// Cloning a client is cheap, and every clone shares the same connection pool and
+/// credentials, so there's no need to wrap it in an `Arc` before handing it to other tasks.
+#[derive(Clone)]
 pub struct RustbloxClient {
     pub(crate) reqwest_client: reqwest::Client,
-    pub(crate) roblox_cookie: Option<String>,
+    // Behind a lock for the same reasons as the csrf token below: a CredentialProvider
+    // can swap it out mid-session without needing &mut self
+    pub(crate) roblox_cookie: Arc<RwLock<Option<String>>>,
+    pub(crate) api_key: Option<String>,
+    pub(crate) oauth: Option<OAuthSession>,
     pub(crate) auto_reauth: bool,
+    pub(crate) validate_names: bool,
+    pub(crate) lazy_csrf: bool,
+    pub(crate) dry_run: bool,
+    pub(crate) challenge_solver: Option<Arc<dyn ChallengeSolver>>,
+    pub(crate) credential_provider: Option<Arc<dyn CredentialProvider>>,
+    pub(crate) auth_callback: Option<AuthCallback>,
+    pub(crate) audit_sink: Option<Arc<dyn AuditSink>>,
+    pub(crate) detect_drift: bool,
+    pub(crate) drift_callback: Option<DriftCallback>,
+    // Which account each cookie belongs to, so audit entries don't need to look it up every time
+    pub(crate) actor_ids: Arc<Mutex<HashMap<String, usize>>>,
+    // Always CSRF_URL outside of tests, which point it at a mock server
+    pub(crate) csrf_url: String,
+    // Always CHALLENGE_CONTINUE_URL outside of tests, for the same reason
+    pub(crate) challenge_continue_url: String,
 
     // So, why Arc<RwLock<Option<String>>>? Let's break this down.
     // TL;DR: session reusability within one client.
@@ -36,40 +119,51 @@ pub struct RustbloxClient {
     //     effectively only permit one request to be processed at a time (subpar!)
     //     - And yes, I do believe the &mut self issue still appears if I instead
     //       make the user have their own Arc<Mutex<RustbloxClient>>
+    //   - It's tokio's RwLock so that the write lock can be held across the refresh
+    //     request. That makes refreshing single-flight: when a bunch of requests get a
+    //     403 at once, the first one refreshes and the rest wait for it, then retry with
+    //     its token instead of each fetching their own.
     // - Option<String>: if you aren't using authentication, you don't need a csrf token
     //
     // I am open to being corrected if this is a terrible solution and
     // I should do it another way.
-    pub(crate) csrf_token: Arc<RwLock<Option<String>>>,
+    pub(crate) csrf_token: Arc<tokio::sync::RwLock<Option<String>>>,
+}
+
+/// Checks a raw `.ROBLOSECURITY` cookie and turns it into a `Cookie` header value.
+pub(crate) fn format_cookie(cookie: &str) -> Result<String, ClientError> {
+    // All .ROBLOSECURITY cookies should start with this
+    // unless Roblox just decides to randomly change it some day
+    // ...which they might
+    (!(cookie.starts_with("_|WARNING")))
+        .then_some(ClientError::InvalidCookie)
+        .map_or(Ok(()), Err)?;
+
+    Ok(format!(".ROBLOSECURITY={cookie}"))
 }
 
 impl RustbloxClient {
-    #[must_use]
-    pub fn csrf_token(&self) -> Option<String> {
-        match self.csrf_token.read() {
-            Ok(inner) => inner.clone(),
-            Err(why) => {
-                warn!("The CSRF RwLock is poisoned:\n{why}");
-                None
-            }
-        }
+    /// Returns the current `x-csrf-token` of this [`RustbloxClient`]. If the token is
+    /// being refreshed, this waits for the new one.
+    pub async fn csrf_token(&self) -> Option<String> {
+        self.csrf_token.read().await.clone()
     }
 
     /// Returns a boolean representing this [`RustbloxClient`]'s authentication status.
-    #[must_use]
-    pub fn is_authenticated(&self) -> bool {
-        let csrf = match self.csrf_token.read() {
-            Ok(inner) => inner.is_some(),
-            Err(why) => {
-                warn!("The CSRF RwLock is poisoned:\n{why}");
-                false
-            }
-        };
-        self.roblox_cookie().is_some() && csrf
+    ///
+    /// With lazy CSRF acquisition turned on, having a cookie is enough, since the
+    /// `x-csrf-token` is fetched on the first request that needs it.
+    pub async fn is_authenticated(&self) -> bool {
+        let csrf = self.csrf_token.read().await.is_some();
+        self.roblox_cookie().is_some() && (csrf || self.lazy_csrf)
     }
 
     /// Logs the client in.
     ///
+    /// The `.ROBLOSECURITY` cookie is checked with a read-only request. Unless
+    /// lazy CSRF acquisition is turned on, an `x-csrf-token` is then fetched with
+    /// a request that has no side effects, even if Roblox were to accept it.
+    ///
     /// # Errors
     ///
     /// This function will return an error if:
@@ -92,54 +186,81 @@ impl RustbloxClient {
 
         debug!("Successfully pinged Roblox");
 
-        if self.roblox_cookie().is_none() {
-            warn!(
-                "[WARN/LOGIN]: Please be advised that you do not have a .ROBLOSECURITY cookie set.\
-                \nYou will not be able to use functions that require this."
-            );
-            return Ok(()); // Return early
-        }
+        let cookie = match self.roblox_cookie() {
+            Some(cookie) => cookie,
+            None => {
+                warn!(
+                    "[WARN/LOGIN]: Please be advised that you do not have a .ROBLOSECURITY cookie set.\
+                    \nYou will not be able to use functions that require this."
+                );
+                return Ok(()); // Return early
+            }
+        };
 
         let auth_response = self
             .reqwest_client
-            .post("https://auth.roblox.com/v2/logout")
-            .header("Content-Length", 0)
-            .header("Cookie", self.roblox_cookie().unwrap())
+            .get("https://users.roblox.com/v1/users/authenticated")
+            .header("Cookie", cookie)
             .send()
             .await
             .map_err(|e| {
                 ClientError::LoginFailed(format!("Authentication request error: \n{e}"))
             })?;
 
-        (auth_response.status() != 403)
+        (auth_response.status() == 401)
             .then_some(ClientError::InvalidCookie)
             .map_or(Ok(()), Err)?;
 
-        std::ops::Not::not(auth_response.headers().contains_key("x-csrf-token"))
-            .then_some(ClientError::LoginFailed(
-                "No x-csrf-token was given by Roblox".to_string(),
-            ))
+        (!auth_response.status().is_success())
+            .then(|| {
+                ClientError::LoginFailed(format!(
+                    "Roblox returned status code {} while checking the cookie",
+                    auth_response.status()
+                ))
+            })
             .map_or(Ok(()), Err)?;
 
-        let csrf_from_headers = auth_response.headers().get("x-csrf-token").unwrap().clone();
+        debug!("The .ROBLOSECURITY cookie is valid");
+
+        if self.lazy_csrf {
+            return Ok(());
+        }
+
+        let mut csrf = self.csrf_token.write().await;
+        *csrf = Some(self.fetch_csrf_token().await?);
+
+        Ok(())
+    }
+
+    /// Gets a fresh `x-csrf-token` from Roblox.
+    ///
+    /// Roblox hands out a token in the headers of any write request sent without one.
+    /// The catalog item details endpoint is used for this since it only reads data,
+    /// so nothing happens if Roblox ever accepts the request anyways.
+    async fn fetch_csrf_token(&self) -> Result<String, ClientError> {
+        let mut request = self
+            .reqwest_client
+            .post(self.csrf_url.as_str())
+            .header("Content-Length", 0);
+        if let Some(cookie) = self.roblox_cookie() {
+            request = request.header("Cookie", cookie);
+        }
+
+        let csrf_response = request
+            .send()
+            .await
+            .map_err(|e| ClientError::LoginFailed(format!("CSRF token request error: \n{e}")))?;
+
+        let csrf_from_headers = csrf_response.headers().get("x-csrf-token").ok_or_else(|| {
+            ClientError::LoginFailed("No x-csrf-token was given by Roblox".to_string())
+        })?;
 
         let csrf_string = csrf_from_headers
             .to_str()
             .map_err(|e| ClientError::LoginFailed(format!("Failed to parse CSRF token:\n{e}")))?
             .to_string();
 
-        let mut csrf = match self.csrf_token.write() {
-            Ok(inner) => inner,
-            Err(why) => {
-                warn!("The CSRF RwLock is poisoned:\n{why}");
-                return Err(ClientError::LoginFailed(
-                    "The CSRF RwLock is poisoned".into(),
-                ));
-            }
-        };
-        *csrf = Some(csrf_string);
-
-        Ok(())
+        Ok(csrf_string)
     }
 
     /// Makes a request to the Roblox API.
@@ -147,17 +268,19 @@ impl RustbloxClient {
     /// `x-csrf-token` is invalid, it will attempt to reauthenticate itself if `Client.auto_reauth`
     /// is set to `true`. This value can be set while building the client.
     /// The Client will only attempt reauthentication once (see the definition of insanity
-    /// for why).
+    /// for why). Replacing an expired cookie or access token is tracked separately in
+    /// `recoveries`, and also only happens once.
     ///
-    /// # Panics
-    ///
-    /// Panics if:
-    /// - An `x-csrf-token` could not be obtained.
+    /// If the client doesn't have an `x-csrf-token` yet (i.e. with lazy CSRF acquisition),
+    /// the token Roblox hands back in the 403 response is stored and the request is retried,
+    /// regardless of `Client.auto_reauth`.
     ///
     /// # Errors
     ///
     /// This function will return an error if:
-    /// - You attempt to contact an endpoint that requires authentication while unauthenticated.
+    /// - You attempt to contact an endpoint that requires authentication without the needed
+    ///   credentials (a `.ROBLOSECURITY` cookie, an API key or an OAuth session).
+    /// - The OAuth access token needed refreshing and that failed.
     /// - The endpoint responds with an error.
     /// - Your `.ROBLOSECURITY` cookie or `x-csrf-token` are invalid and automatic reauthentication failed
     /// or was not enabled. In either case, you will get a [`RequestError::ReauthenticationFailed`].
@@ -165,22 +288,49 @@ impl RustbloxClient {
     pub(crate) async fn make_request<T>(
         &self,
         components: RequestComponents,
-        tried_reauth: bool,
+        recoveries: Recoveries,
     ) -> Result<T, RequestError>
     where
         T: DeserializeOwned,
     {
-        (components.needs_auth && self.roblox_cookie().is_none())
+        let sent_cookie = self.roblox_cookie();
+        (components.auth == AuthMethod::Cookie && sent_cookie.is_none())
+            .then_some(RequestError::NotAuthenticated)
+            .map_or(Ok(()), Err)?;
+        (components.auth == AuthMethod::ApiKey && self.api_key().is_none())
+            .then_some(RequestError::NotAuthenticated)
+            .map_or(Ok(()), Err)?;
+        (components.auth == AuthMethod::OAuth && self.oauth_session().is_none())
             .then_some(RequestError::NotAuthenticated)
             .map_or(Ok(()), Err)?;
 
+        if self.dry_run && components.mutating {
+            return Self::dry_run_response::<T>(components);
+        }
+
         let mut request = self
             .reqwest_client
             .request(components.method.clone(), components.url.clone());
-        if components.needs_auth {
-            request = request
-                .header("Cookie", self.roblox_cookie().unwrap())
-                .header("x-csrf-token", self.csrf_token().unwrap());
+        // With lazy CSRF acquisition, there won't be a token until the first
+        // write request gets one from the 403 handshake below
+        let sent_csrf = self.csrf_token().await;
+        let mut sent_access_token = None;
+        match components.auth {
+            AuthMethod::None => {}
+            AuthMethod::Cookie => {
+                request = request.header("Cookie", sent_cookie.clone().unwrap());
+                if let Some(csrf) = &sent_csrf {
+                    request = request.header("x-csrf-token", csrf);
+                }
+            }
+            AuthMethod::ApiKey => {
+                request = request.header("x-api-key", self.api_key().unwrap());
+            }
+            AuthMethod::OAuth => {
+                let access_token = self.oauth_session().unwrap().access_token(self).await?;
+                request = request.bearer_auth(&access_token);
+                sent_access_token = Some(access_token);
+            }
         }
 
         if components.headers.is_some() {
@@ -196,108 +346,328 @@ impl RustbloxClient {
             .map_err(|e| RequestError::RequestError(components.url.clone(), e.to_string()))?;
 
         if !response.status().is_success() {
+            if let Some(challenge) = Challenge::from_headers(response.headers()) {
+                return self
+                    .handle_challenge::<T>(components, challenge, recoveries)
+                    .await;
+            }
+
             let status_code = response.status().as_u16();
-            return if response.status().is_client_error() {
-                if status_code == 401 {
-                    // Bad cookie
-                    return Err(RequestError::ExpiredCookie);
-                }
+            let mut err_h = response.headers().clone();
+            // Roblox sometimes hands out a new cookie with a response. Errors get logged,
+            // so keep it out of them
+            err_h.remove(SET_COOKIE);
+            let err_body_text = response.text().await.map_err(|e| {
+                RequestError::RequestError(
+                    components.url.clone(),
+                    format!("Failed to get error response body:\n{}", e),
+                )
+            })?;
+            // Error bodies are usually { "errors": [{ "code": number, "message": string }] },
+            // but not always (i.e. OAuth errors, or plain text from a load balancer)
+            let err_body =
+                serde_json::from_str::<RobloxApiErrors>(err_body_text.as_str()).unwrap_or_default();
+            let error = Box::new(HttpError {
+                method: components.method.clone(),
+                url: components.url.clone(),
+                status: status_code,
+                errors: err_body,
+                body: err_body_text,
+                headers: err_h,
+            });
 
-                // Have to parse as serde_json::Value because SPECIFICALLY
-                // 403 Token Validation Failed has different JSON than the
-                // rest of the errors
-                let err_h = response.headers().clone();
-                let err_body_text = response.text().await.map_err(|e| {
-                    RequestError::RequestError(
-                        components.url.clone(),
-                        format!("Failed to get error response body:\n{}", e),
-                    )
-                })?;
-                let err_body = serde_json::from_str::<RobloxApiErrors>(err_body_text.as_str()).map_err(|e| {
-                    RequestError::RequestError(
-                        components.url.clone(),
-                        format!("Couldn't parse error body as RobloxApiErrors:\n{}Response body:\n{}", e, err_body_text),
-                    )
-                })?;
-
-                if status_code == 403 {
-                    // We need to find out if the Roblox API wants us to reauthenticate or if
-                    // the error is for a different reason
-                    if err_body.errors.first().is_some() {
-                        // This means that we *probably* have a 403 Token Validation Failed
-                        // since the json is just { code: number, message: string }
-                        if err_body.errors.first().unwrap().code != 0 {
-                            return Err(RequestError::RequestError(
-                                components.url.clone(),
-                                format!("Unknown 403 error:\n{}", err_body_text),
-                            ));
-                        }
-
-                        // Now we definitely have a 403 Token Validation Failed
-                        if self.auto_reauth && !tried_reauth {
-                            if err_h.contains_key("x-csrf-token") {
-                                let header_csrf =
-                                    err_h.get("x-csrf-token").unwrap().to_str().unwrap();
-                                let mut self_csrf = self.csrf_token.write().map_err(|e| {
-                                    RequestError::ReauthenticationFailed(e.to_string())
-                                })?;
-                                *self_csrf = Some(header_csrf.into());
-                            } else {
-                                self.login().await.map_err(|e| {
-                                    RequestError::ReauthenticationFailed(e.to_string())
-                                })?;
-                            }
-
-                            return self.make_request::<T>(components, true).await;
-                        }
+            if status_code == 401 {
+                if let Some(access_token) = sent_access_token {
+                    // The access token was revoked or expired early, so get a new one
+                    // and try again
+                    if recoveries.credentials {
                         return Err(RequestError::ReauthenticationFailed(
-                            "Automatic reauthentication either not enabled or already tried"
-                                .to_string(),
+                            "Roblox rejected the refreshed OAuth access token".to_string(),
                         ));
                     }
+                    self.oauth_session()
+                        .unwrap()
+                        .refresh_rejected(self, &access_token)
+                        .await?;
+                    let recoveries = Recoveries {
+                        credentials: true,
+                        ..recoveries
+                    };
+                    return self.make_request::<T>(components, recoveries).await;
                 }
 
-                Err(RequestError::ClientError(
-                    components.url,
-                    status_code,
-                    err_body,
-                ))
-            } else if response.status().is_server_error() {
-                Err(RequestError::ServerError(status_code))
+                if components.auth != AuthMethod::Cookie {
+                    return Err(RequestError::Unauthorized(error));
+                }
+                if let Some(cookie) = sent_cookie {
+                    // Bad cookie, so see if there's a fresh one to try
+                    self.emit_auth_event(AuthEvent::Expired);
+                    if !recoveries.credentials && self.refresh_cookie(&cookie).await? {
+                        let recoveries = Recoveries {
+                            credentials: true,
+                            ..recoveries
+                        };
+                        return self.make_request::<T>(components, recoveries).await;
+                    }
+                }
+                return Err(RequestError::ExpiredCookie(error));
+            }
+
+            if status_code == 403 {
+                // 403 Token Validation Failed always has code 0
+                if components.auth == AuthMethod::Cookie && error.code() == Some(0) {
+                    // If we never sent a token, this is just the lazy handshake and
+                    // not a failed authentication, so it doesn't need auto_reauth
+                    if (self.auto_reauth || sent_csrf.is_none()) && !recoveries.csrf {
+                        let header_csrf = error
+                            .headers
+                            .get("x-csrf-token")
+                            .and_then(|value| value.to_str().ok())
+                            .map(String::from);
+                        self.refresh_csrf_token(sent_csrf.as_deref(), header_csrf)
+                            .await?;
+
+                        let recoveries = Recoveries {
+                            csrf: true,
+                            ..recoveries
+                        };
+                        return self.make_request::<T>(components, recoveries).await;
+                    }
+                    return Err(RequestError::ReauthenticationFailed(
+                        "Automatic reauthentication either not enabled or already tried"
+                            .to_string(),
+                    ));
+                }
+
+                // Roblox doesn't use one code for missing permissions, so every endpoint
+                // says which of its codes mean that
+                let lacks_permission = error
+                    .errors
+                    .errors
+                    .iter()
+                    .any(|e| components.permission_error_codes.contains(&e.code));
+                return Err(if lacks_permission {
+                    RequestError::InsufficientPermissions(error)
+                } else {
+                    RequestError::Forbidden(error)
+                });
+            }
+
+            return Err(if error.status >= 500 {
+                RequestError::ServerError(error)
             } else {
-                let body = response.text().await.map_err(|e| {
-                    RequestError::RequestError(
-                        components.url.clone(),
-                        format!("Couldn't parse body as string:\n{}", e),
-                    )
-                })?;
-
-                let unknown_error = RobloxApiError {
-                    code: -999,
-                    message: body,
-                };
-                let error_struct = RobloxApiErrors {
-                    errors: vec![unknown_error],
-                };
-                Err(RequestError::ClientError(
-                    components.url,
-                    status_code,
-                    error_struct,
-                ))
-            };
+                RequestError::ClientError(error)
+            });
         }
 
-        let response_data = response
-            .json::<T>()
+        let body = response
+            .text()
             .await
-            .map_err(|e| RequestError::RequestError(components.url, e.to_string()))?;
+            .map_err(|e| RequestError::RequestError(components.url.clone(), e.to_string()))?;
+        // Some endpoints (i.e. OAuth token revocation) respond with an empty body
+        let body = if body.is_empty() {
+            "null"
+        } else {
+            body.as_str()
+        };
+        let response_data = DeserializeError::parse::<T>(components.url.as_str(), body)
+            .map_err(RequestError::Deserialize)?;
+
+        if self.detect_drift {
+            if let Some(drift) = components
+                .drift_check
+                .and_then(|check| check(components.url.as_str(), body))
+            {
+                self.report_schema_drift(&drift);
+            }
+        }
 
         Ok(response_data)
     }
 
+    /// Replaces the `x-csrf-token` that Roblox rejected (`stale`), using the token from
+    /// the 403 response (`offered`) if there was one. Only one refresh happens at a time;
+    /// if another request already replaced `stale` while this one waited, its token is
+    /// kept instead.
+    async fn refresh_csrf_token(
+        &self,
+        stale: Option<&str>,
+        offered: Option<String>,
+    ) -> Result<(), RequestError> {
+        let mut csrf = self.csrf_token.write().await;
+        if csrf.as_deref() != stale {
+            debug!("The x-csrf-token was already refreshed by another request");
+            return Ok(());
+        }
+
+        let token = match offered {
+            Some(token) => token,
+            None => self
+                .fetch_csrf_token()
+                .await
+                .map_err(|e| RequestError::ReauthenticationFailed(e.to_string()))?,
+        };
+        *csrf = Some(token);
+        drop(csrf);
+
+        // Without a stale token, this was just the lazy handshake
+        if stale.is_some() {
+            self.emit_auth_event(AuthEvent::Reauthenticated);
+        }
+        Ok(())
+    }
+
+    /// Asks the [`CredentialProvider`], if there is one, for a fresh `.ROBLOSECURITY`
+    /// cookie to replace `rejected`. Returns whether or not the client has a new cookie
+    /// to retry with.
+    async fn refresh_cookie(&self, rejected: &str) -> Result<bool, RequestError> {
+        let provider = match &self.credential_provider {
+            Some(provider) => provider,
+            None => return Ok(false),
+        };
+
+        debug!("Asking the credential provider for a fresh cookie");
+        let cookie = match provider.cookie().await? {
+            Some(cookie) => format_cookie(&cookie)
+                .map_err(|e| RequestError::ReauthenticationFailed(e.to_string()))?,
+            None => return Ok(false),
+        };
+        if cookie == rejected {
+            // Retrying with the cookie that just got rejected won't help
+            return Ok(false);
+        }
+
+        self.store_cookie(cookie)
+            .await
+            .map_err(|e| RequestError::ReauthenticationFailed(e.to_string()))?;
+
+        self.emit_auth_event(AuthEvent::Refreshed);
+        Ok(true)
+    }
+
+    /// Replaces the `Cookie` header value that every clone of this client sends.
+    async fn store_cookie(&self, cookie: String) -> Result<(), ClientError> {
+        *self
+            .roblox_cookie
+            .write()
+            .map_err(|e| ClientError::CookieError(e.to_string()))? = Some(cookie);
+        // The old x-csrf-token belonged to the old session. The next write request picks
+        // up a new one the same way lazy CSRF acquisition does
+        *self.csrf_token.write().await = None;
+        Ok(())
+    }
+
+    fn emit_auth_event(&self, event: AuthEvent) {
+        debug!("Auth event: {event:?}");
+        if let Some(callback) = &self.auth_callback {
+            callback(event);
+        }
+    }
+
+    /// Logs a mutating request instead of sending it, and makes up a successful response.
+    /// Only the method, URL and (for endpoints) what the request acts on are logged.
+    /// The response is parsed from `null`. Every built-in mutating endpoint accepts that, and
+    /// [`Endpoint::Response`](crate::endpoint::Endpoint::Response) asks the same of custom ones.
+    fn dry_run_response<T>(components: RequestComponents) -> Result<T, RequestError>
+    where
+        T: DeserializeOwned,
+    {
+        info!("[DRY RUN] Would have sent {}", components.dry_run_summary());
+
+        serde_json::from_value::<T>(serde_json::Value::Null).map_err(|e| {
+            RequestError::RequestError(
+                components.url,
+                format!(
+                    "Couldn't make a dry-run response for this request (its response type \
+                     has to accept null):\n{e}"
+                ),
+            )
+        })
+    }
+
+    /// Hands a challenge to the registered [`ChallengeSolver`], if there is one, and
+    /// resends the request with the solution. Each request is only retried once.
+    async fn handle_challenge<T>(
+        &self,
+        mut components: RequestComponents,
+        challenge: Challenge,
+        recoveries: Recoveries,
+    ) -> Result<T, RequestError>
+    where
+        T: DeserializeOwned,
+    {
+        let already_tried = matches!(
+            &components.headers,
+            Some(headers) if headers.contains_key(CHALLENGE_ID_HEADER)
+        );
+        let solver = match &self.challenge_solver {
+            Some(solver) if !already_tried => solver,
+            _ => return Err(RequestError::ChallengeRequired(components.url, challenge)),
+        };
+
+        debug!(
+            "Solving a {} challenge for {}",
+            challenge.challenge_type.as_str(),
+            components.url
+        );
+        let solution = match solver.solve(self, &challenge).await? {
+            Some(solution) => solution,
+            None => return Err(RequestError::ChallengeRequired(components.url, challenge)),
+        };
+        self.continue_challenge(&solution, components.auth).await?;
+
+        let solution_headers = solution.headers().map_err(|e| {
+            RequestError::RequestError(
+                components.url.clone(),
+                format!("Couldn't build the challenge solution headers:\n{e}"),
+            )
+        })?;
+        components
+            .headers
+            .get_or_insert_with(HeaderMap::new)
+            .extend(solution_headers);
+
+        self.make_request::<T>(components, recoveries).await
+    }
+
+    /// Returns whether or not this [`RustbloxClient`] is in dry-run mode.
+    #[must_use]
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Returns the Open Cloud API key of this [`RustbloxClient`].
+    #[must_use]
+    pub fn api_key(&self) -> Option<&String> {
+        self.api_key.as_ref()
+    }
+
+    /// Returns the OAuth session of this [`RustbloxClient`].
+    #[must_use]
+    pub fn oauth_session(&self) -> Option<&OAuthSession> {
+        self.oauth.as_ref()
+    }
+
+    /// Replaces the `.ROBLOSECURITY` cookie of this [`RustbloxClient`] and every clone of it.
+    /// The `x-csrf-token` is cleared, since it belonged to the old session; the next write
+    /// request picks up a new one.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the cookie provided is invalid.
+    pub async fn set_roblox_cookie(&self, cookie: &str) -> Result<(), ClientError> {
+        self.store_cookie(format_cookie(cookie)?).await
+    }
+
     /// Returns the roblox cookie of this [`RustbloxClient`].
     #[must_use]
-    pub fn roblox_cookie(&self) -> Option<&String> {
-        self.roblox_cookie.as_ref()
+    pub fn roblox_cookie(&self) -> Option<String> {
+        match self.roblox_cookie.read() {
+            Ok(inner) => inner.clone(),
+            Err(why) => {
+                warn!("The cookie RwLock is poisoned:\n{why}");
+                None
+            }
+        }
     }
 }
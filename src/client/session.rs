@@ -0,0 +1,89 @@
+use crate::challenge::ChallengeSolver;
+use crate::client::{format_cookie, RustbloxClient};
+use crate::credentials::CredentialProvider;
+use crate::error::ClientError;
+use std::sync::{Arc, RwLock};
+
+/// The credentials of another Roblox account, for running calls as that account with
+/// [`as_account`](RustbloxClient::as_account).
+///
+/// A session keeps its own `x-csrf-token`, so keep it around between calls instead of
+/// creating a new one each time. Clones share the same credentials.
+#[derive(Clone)]
+pub struct AccountSession {
+    roblox_cookie: Arc<RwLock<Option<String>>>,
+    csrf_token: Arc<tokio::sync::RwLock<Option<String>>>,
+    challenge_solver: Option<Arc<dyn ChallengeSolver>>,
+    credential_provider: Option<Arc<dyn CredentialProvider>>,
+}
+
+impl std::fmt::Debug for AccountSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Don't leak the cookie into logs
+        f.debug_struct("AccountSession").finish_non_exhaustive()
+    }
+}
+
+impl AccountSession {
+    /// Creates a session for the account that `cookie` (a `.ROBLOSECURITY` cookie)
+    /// belongs to.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the cookie provided is invalid.
+    pub fn new(cookie: &str) -> Result<Self, ClientError> {
+        Ok(Self {
+            roblox_cookie: Arc::new(RwLock::new(Some(format_cookie(cookie)?))),
+            csrf_token: Arc::new(tokio::sync::RwLock::new(None)),
+            challenge_solver: None,
+            credential_provider: None,
+        })
+    }
+
+    /// Sets the [`ChallengeSolver`] used for this account's challenges. The client's own
+    /// solver is never used for other accounts, since it is usually tied to the client's
+    /// account (i.e. its authenticator secret).
+    #[must_use]
+    pub fn challenge_solver(mut self, solver: impl ChallengeSolver + 'static) -> Self {
+        self.challenge_solver = Some(Arc::new(solver));
+        self
+    }
+
+    /// Sets the [`CredentialProvider`] asked for a fresh cookie for this account. The
+    /// client's own provider is never used for other accounts.
+    #[must_use]
+    pub fn credential_provider(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.credential_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Returns the roblox cookie of this [`AccountSession`].
+    #[must_use]
+    pub fn roblox_cookie(&self) -> Option<String> {
+        match self.roblox_cookie.read() {
+            Ok(inner) => inner.clone(),
+            Err(why) => {
+                warn!("The cookie RwLock is poisoned:\n{why}");
+                None
+            }
+        }
+    }
+}
+
+impl RustbloxClient {
+    /// Returns a client that runs calls as the account of `session`, i.e.
+    /// `client.as_account(&session).kick_user(...)`.
+    ///
+    /// The returned client shares this client's connection pool and settings, but uses
+    /// the session's `.ROBLOSECURITY` cookie, `x-csrf-token`, challenge solver and credential
+    /// provider. Cookie updates and refreshed tokens are stored in the session.
+    #[must_use]
+    pub fn as_account(&self, session: &AccountSession) -> RustbloxClient {
+        let mut client = self.clone();
+        client.roblox_cookie = Arc::clone(&session.roblox_cookie);
+        client.csrf_token = Arc::clone(&session.csrf_token);
+        client.challenge_solver = session.challenge_solver.clone();
+        client.credential_provider = session.credential_provider.clone();
+        client
+    }
+}
@@ -0,0 +1,69 @@
+//! Keeping a long-running client authenticated.
+//!
+//! `.ROBLOSECURITY` cookies expire or get invalidated (i.e. when the account logs out
+//! everywhere). When Roblox rejects the client's cookie, the client asks its
+//! [`CredentialProvider`], if one was registered with
+//! [`RustbloxClientBuilder::credential_provider`](crate::client::builder::RustbloxClientBuilder::credential_provider),
+//! for a fresh one and retries the request once. Applications can also watch for these
+//! moments with
+//! [`RustbloxClientBuilder::on_auth_event`](crate::client::builder::RustbloxClientBuilder::on_auth_event).
+
+use crate::error::RequestError;
+use std::path::PathBuf;
+
+/// Something that happened to a client's credentials.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AuthEvent {
+    /// Roblox rejected the `.ROBLOSECURITY` cookie.
+    Expired,
+    /// A fresh `.ROBLOSECURITY` cookie was fetched from the [`CredentialProvider`].
+    Refreshed,
+    /// A fresh `x-csrf-token` was fetched after Roblox rejected the old one.
+    Reauthenticated,
+}
+
+/// Hands a client fresh credentials after Roblox rejects the ones it has.
+#[async_trait::async_trait]
+pub trait CredentialProvider: Send + Sync {
+    /// Returns a fresh `.ROBLOSECURITY` cookie (without the `.ROBLOSECURITY=` prefix).
+    ///
+    /// Return `Ok(None)` if there is no fresh cookie to be had. The original request then
+    /// fails with [`RequestError::ExpiredCookie`].
+    ///
+    /// # Errors
+    ///
+    /// Any error returned here is returned from the original request.
+    async fn cookie(&self) -> Result<Option<String>, RequestError>;
+}
+
+/// A [`CredentialProvider`] that reads the `.ROBLOSECURITY` cookie from a file every time
+/// it is asked. Whatever keeps the file up to date (i.e. a secrets manager agent) doesn't
+/// need to know about the client at all.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CookieFile {
+    path: PathBuf,
+}
+
+impl CookieFile {
+    /// Creates a provider that reads the cookie from `path`.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait::async_trait]
+impl CredentialProvider for CookieFile {
+    async fn cookie(&self) -> Result<Option<String>, RequestError> {
+        let cookie = tokio::fs::read_to_string(&self.path).await.map_err(|e| {
+            RequestError::ReauthenticationFailed(format!(
+                "Couldn't read the cookie from {}:\n{e}",
+                self.path.display()
+            ))
+        })?;
+        let cookie = cookie.trim();
+
+        Ok((!cookie.is_empty()).then(|| cookie.to_string()))
+    }
+}
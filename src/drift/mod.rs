@@ -0,0 +1,149 @@
+//! Spotting changes to the shape of Roblox's responses.
+//!
+//! Roblox changes its responses without warning (renamed fields, new fields, fields that
+//! quietly stop being sent). Most of these don't break parsing, so they'd otherwise go
+//! unnoticed until something does. With
+//! [`detect_schema_drift`](crate::client::builder::RustbloxClientBuilder::detect_schema_drift)
+//! enabled, each response to an [`Endpoint`](crate::endpoint::Endpoint) is also compared
+//! with the type it is parsed into, and any differences are reported as a [`SchemaDrift`].
+//! Requests never fail because of drift.
+
+use crate::client::RustbloxClient;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+pub(crate) type DriftCallback = Arc<dyn Fn(&SchemaDrift) + Send + Sync>;
+
+/// The ways a response differed from the type it was parsed into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SchemaDrift {
+    /// The URL the response came from.
+    pub url: String,
+    /// The type the response was parsed into.
+    pub target: &'static str,
+    /// Fields in the response that the type doesn't have (i.e. `data[0].hasVerifiedBadge`).
+    pub unknown_fields: Vec<String>,
+    /// Fields the type has that weren't in the response, and so were filled in with
+    /// their defaults (usually `None`).
+    pub missing_fields: Vec<String>,
+}
+
+impl Display for SchemaDrift {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The response from {} has drifted from {}",
+            self.url, self.target
+        )?;
+        if !self.unknown_fields.is_empty() {
+            write!(f, "\nUnknown fields: {}", self.unknown_fields.join(", "))?;
+        }
+        if !self.missing_fields.is_empty() {
+            write!(f, "\nMissing fields: {}", self.missing_fields.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares `body` with `T`. Returns None if they match, or if the body doesn't parse
+/// as a `T` at all (that's an error, not drift).
+pub(crate) fn check<T>(url: &str, body: &str) -> Option<SchemaDrift>
+where
+    T: DeserializeOwned + Serialize,
+{
+    let raw = serde_json::from_str::<Value>(body).ok()?;
+
+    let mut unknown_fields = Vec::new();
+    let parsed: T = serde_ignored::deserialize(raw.clone(), |path| {
+        let mut formatted = String::new();
+        format_path(&path, &mut formatted);
+        unknown_fields.push(formatted);
+    })
+    .ok()?;
+
+    // Anything the type would send back that the response didn't have was filled in
+    let mut missing_fields = Vec::new();
+    if let Ok(expected) = serde_json::to_value(&parsed) {
+        find_missing(&expected, &raw, "", &mut missing_fields);
+    }
+
+    if unknown_fields.is_empty() && missing_fields.is_empty() {
+        return None;
+    }
+    Some(SchemaDrift {
+        url: url.to_string(),
+        target: std::any::type_name::<T>(),
+        unknown_fields,
+        missing_fields,
+    })
+}
+
+/// Writes `path` in the same form as [`DeserializeError::path`](crate::error::DeserializeError::path).
+fn format_path(path: &serde_ignored::Path<'_>, out: &mut String) {
+    use serde_ignored::Path;
+
+    match path {
+        Path::Root => {}
+        Path::Seq { parent, index } => {
+            format_path(parent, out);
+            out.push_str(format!("[{index}]").as_str());
+        }
+        Path::Map { parent, key } => {
+            format_path(parent, out);
+            if !out.is_empty() {
+                out.push('.');
+            }
+            out.push_str(key);
+        }
+        Path::Some { parent }
+        | Path::NewtypeStruct { parent }
+        | Path::NewtypeVariant { parent } => {
+            format_path(parent, out);
+        }
+    }
+}
+
+fn find_missing(expected: &Value, raw: &Value, path: &str, missing: &mut Vec<String>) {
+    match (expected, raw) {
+        (Value::Object(expected), Value::Object(raw)) => {
+            for (key, expected_value) in expected {
+                let field_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match raw.get(key) {
+                    Some(raw_value) => {
+                        find_missing(expected_value, raw_value, &field_path, missing)
+                    }
+                    None => missing.push(field_path),
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(raw)) => {
+            for (index, (expected_value, raw_value)) in expected.iter().zip(raw).enumerate() {
+                find_missing(
+                    expected_value,
+                    raw_value,
+                    &format!("{path}[{index}]"),
+                    missing,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+impl RustbloxClient {
+    /// Logs `drift` and hands it to the drift callback, if there is one.
+    pub(crate) fn report_schema_drift(&self, drift: &SchemaDrift) {
+        warn!("{drift}");
+        if let Some(callback) = &self.drift_callback {
+            callback(drift);
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! Declarative descriptions of the Roblox API endpoints that Rustblox supports.
+//!
+//! Every function on the [`RustbloxClient`] that contacts the Roblox API is backed
+//! by a type implementing [`Endpoint`]. These can also be run directly with
+//! [`execute`](RustbloxClient::execute), which is handy when you want to build up
+//! requests ahead of time (i.e. for batching, caching or mocking).
+
+use crate::audit::{AuditEntry, AuditOutcome};
+use crate::client::{AuthMethod, Method, RustbloxClient};
+use crate::error::RequestError;
+use crate::structs::SortOrder;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+mod url;
+
+pub use url::UrlBuilder;
+
+pub use crate::routes::authenticated::auth::{
+    ChangeUsername, GetExistingUsernames, ValidateUsername,
+};
+pub use crate::routes::authenticated::cloud::IntrospectApiKey;
+pub use crate::routes::authenticated::economy::GetUserCurrency;
+pub use crate::routes::authenticated::group::membership::{
+    AcceptUserJoinRequest, BatchAcceptRequests, BatchDenyRequests, BatchGetRequests,
+    DenyUserJoinRequest, GetUserJoinRequest, KickUser, SetUserRoleInGroup,
+};
+pub use crate::routes::authenticated::oauth::GetOAuthUserInfo;
+pub use crate::routes::authenticated::twostepverification::VerifyAuthenticatorCode;
+pub use crate::routes::authenticated::user::{
+    ChangeDisplayName, GetAuthenticatedUser, GetAuthenticatedUserAgeBracket,
+    GetAuthenticatedUserBirthdate, GetAuthenticatedUserCountryCode, GetAuthenticatedUserGender,
+    GetAuthenticatedUserRoles, GetUserDescription, SetAuthenticatedUserBirthdate,
+    SetAuthenticatedUserDescription, SetAuthenticatedUserGender, ValidateUserDisplayName,
+};
+pub use crate::routes::unauthenticated::group::groups::GetGroupInfo;
+pub use crate::routes::unauthenticated::group::membership::{
+    GetGroupMembers, GetGroupRoleMembers, GetGroupRoles, GetUserGroupRoles,
+};
+pub use crate::routes::unauthenticated::oauth::{
+    ExchangeOAuthCode, IntrospectOAuthToken, RefreshOAuthToken, RevokeOAuthToken,
+};
+pub use crate::routes::unauthenticated::presence::{
+    GetLastOnline, GetUserPresences, PRESENCE_BATCH_SIZE,
+};
+pub use crate::routes::unauthenticated::user::{
+    GetPreviousUsernames, GetUserInfo, GetUsersFromIds, GetUsersFromUsernames, SearchUser,
+};
+
+/// How an [`Endpoint`]'s body is encoded.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BodyFormat {
+    /// The body is sent as JSON.
+    #[default]
+    Json,
+    /// The body is sent as `application/x-www-form-urlencoded`.
+    Form,
+}
+
+/// Describes a single Roblox API endpoint: where it lives, how to call it,
+/// and what it sends back.
+pub trait Endpoint {
+    /// The body sent with the request. Use `()` for endpoints without one.
+    type Body: Serialize;
+    /// The type the response body is parsed into.
+    ///
+    /// In [dry-run mode](crate::client::builder::RustbloxClientBuilder::dry_run), mutating
+    /// endpoints aren't sent and their response is parsed from `null` instead. So if
+    /// [`mutating`](Endpoint::mutating) is true, this has to accept `null`: use
+    /// `serde_json::Value`, `()` or an `Option`, otherwise dry runs fail with a
+    /// [`RequestError::RequestError`](crate::error::RequestError::RequestError).
+    type Response: DeserializeOwned + Serialize;
+
+    /// The HTTP method used by this endpoint.
+    fn method(&self) -> Method;
+
+    /// The URL of this endpoint, without any query parameters.
+    fn url(&self) -> String;
+
+    /// The credentials this endpoint needs. [`AuthMethod::None`] by default.
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::None
+    }
+
+    /// The query parameters sent with the request. Empty by default.
+    fn query(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// The body sent with the request. None by default.
+    fn body(&self) -> Option<&Self::Body> {
+        None
+    }
+
+    /// How the body is encoded. [`BodyFormat::Json`] by default.
+    fn body_format(&self) -> BodyFormat {
+        BodyFormat::Json
+    }
+
+    /// Whether or not this endpoint changes anything on Roblox's end. Mutating endpoints
+    /// are skipped in [dry-run mode](crate::client::builder::RustbloxClientBuilder::dry_run).
+    /// By default, every endpoint that doesn't use `GET`, `HEAD`, `OPTIONS` or `TRACE` is
+    /// considered mutating, the same as with [`RustbloxRequest::mutating`](crate::client::RustbloxRequest::mutating).
+    /// Endpoints that only read through another method (i.e. a `POST` lookup) say so here.
+    fn mutating(&self) -> bool {
+        !self.method().is_safe()
+    }
+
+    /// What this endpoint acts on (i.e. `[("group_id", 1.into())]`), for the
+    /// [audit journal](crate::audit). Every endpoint that doesn't use `GET` is recorded,
+    /// including read-only ones. Empty by default.
+    fn audit_targets(&self) -> Vec<(&'static str, serde_json::Value)> {
+        Vec::new()
+    }
+
+    /// The error codes Roblox documents for this endpoint's `403`s when the account lacks a
+    /// permission (i.e. `[4]` for "You do not have permission to manage this member."). Those
+    /// become [`RequestError::InsufficientPermissions`]; every other `403` is
+    /// [`RequestError::Forbidden`]. Empty by default.
+    fn permission_error_codes(&self) -> Vec<i16> {
+        Vec::new()
+    }
+}
+
+impl RustbloxClient {
+    /// Runs an [`Endpoint`] and returns its parsed response.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The endpoint needs credentials (a `.ROBLOSECURITY` cookie, an API key or an OAuth session) that you do not have set.
+    /// - The endpoint responds with an error.
+    pub async fn execute<E>(&self, endpoint: E) -> Result<E::Response, RequestError>
+    where
+        E: Endpoint,
+    {
+        self.execute_with_previous_state(endpoint, None).await
+    }
+
+    /// Runs an [`Endpoint`], recording it in the [audit journal](crate::audit) along with
+    /// `previous_state` if it doesn't use `GET` and the client is audited.
+    pub(crate) async fn execute_with_previous_state<E>(
+        &self,
+        endpoint: E,
+        previous_state: Option<serde_json::Value>,
+    ) -> Result<E::Response, RequestError>
+    where
+        E: Endpoint,
+    {
+        let mut request = self
+            .request::<E::Response>(endpoint.method(), endpoint.url())
+            .auth(endpoint.auth())
+            .mutating(endpoint.mutating())
+            .action(format!(
+                "{} {}",
+                action_name::<E>(),
+                serde_json::Value::Object(audit_targets(&endpoint))
+            ))
+            .permission_error_codes(&endpoint.permission_error_codes())
+            .drift_checked();
+        for (key, value) in endpoint.query() {
+            request = request.query(key, value);
+        }
+        if let Some(body) = endpoint.body() {
+            request = match endpoint.body_format() {
+                BodyFormat::Json => request.json(body),
+                BodyFormat::Form => request.form(body),
+            };
+        }
+
+        let result = request.send().await;
+        if endpoint.method() != Method::GET && self.is_audited() {
+            self.audit(&endpoint, previous_state, &result).await;
+        }
+        result
+    }
+
+    async fn audit<E, T>(
+        &self,
+        endpoint: &E,
+        previous_state: Option<serde_json::Value>,
+        result: &Result<T, RequestError>,
+    ) where
+        E: Endpoint,
+    {
+        let actor_id = match endpoint.auth() {
+            AuthMethod::Cookie => self.audit_actor_id().await,
+            _ => None,
+        };
+        let outcome = match result {
+            // Read-only calls are still sent in dry-run mode
+            Ok(_) if self.is_dry_run() && endpoint.mutating() => AuditOutcome::DryRun,
+            Ok(_) => AuditOutcome::Succeeded,
+            Err(why) => AuditOutcome::Failed(why.to_string()),
+        };
+
+        self.record_audit_entry(AuditEntry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default(),
+            actor_id,
+            action: action_name::<E>().to_string(),
+            targets: audit_targets(endpoint),
+            previous_state,
+            outcome,
+        })
+        .await;
+    }
+}
+
+/// Names an endpoint after its type (i.e. `KickUser`), for logs and audit entries.
+fn action_name<E>() -> &'static str {
+    std::any::type_name::<E>()
+        .rsplit("::")
+        .next()
+        .unwrap_or_default()
+}
+
+fn audit_targets<E>(endpoint: &E) -> serde_json::Map<String, serde_json::Value>
+where
+    E: Endpoint,
+{
+    endpoint
+        .audit_targets()
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect()
+}
+
+/// Builds the query parameters shared by the paginated endpoints.
+pub(crate) fn page_query(
+    limit: Option<usize>,
+    cursor: Option<&String>,
+    sort_order: Option<SortOrder>,
+) -> Vec<(&'static str, String)> {
+    let mut query = vec![("limit", limit.unwrap_or(10).to_string())];
+    if let Some(cursor) = cursor {
+        query.push(("cursor", cursor.clone()));
+    }
+    if let Some(sort_order) = sort_order {
+        let sort_order = match sort_order {
+            SortOrder::Ascending => "Asc",
+            SortOrder::Descending => "Desc",
+        };
+        query.push(("sortOrder", sort_order.to_string()));
+    }
+    query
+}
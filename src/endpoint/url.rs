@@ -0,0 +1,66 @@
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::fmt::Display;
+
+// Everything but the characters RFC 3986 calls unreserved, so a value can't end a
+// path segment (/), start a query (?) or fragment (#), or smuggle in an escape (%)
+const SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Builds endpoint URLs out of a base, fixed path pieces and values, percent-encoding
+/// the values so that they always stay inside their own path segment.
+///
+/// Query parameters don't go here: [`Endpoint::query`](crate::endpoint::Endpoint::query)
+/// values are encoded when the request is sent.
+///
+/// ```
+/// use rustblox::endpoint::UrlBuilder;
+///
+/// let url = UrlBuilder::new("https://users.roblox.com/v1")
+///     .path("users")
+///     .segment("a/b?c")
+///     .build();
+/// assert_eq!(url, "https://users.roblox.com/v1/users/a%2Fb%3Fc");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use = "URLs do nothing until they are built"]
+pub struct UrlBuilder {
+    url: String,
+}
+
+impl UrlBuilder {
+    /// Starts a URL at `base`, which is used as-is.
+    pub fn new(base: &str) -> Self {
+        Self {
+            url: base.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Appends a fixed piece of path (i.e. `v1/groups`), which is used as-is. Never pass
+    /// user input here; use [`segment`](UrlBuilder::segment) instead.
+    pub fn path(mut self, path: &str) -> Self {
+        self.url.push('/');
+        self.url.push_str(path.trim_matches('/'));
+        self
+    }
+
+    /// Appends `value` as a single percent-encoded path segment.
+    ///
+    /// A value of exactly `.` or `..` still moves around the path once the URL is parsed
+    /// (encoding doesn't help, since parsers decode dot segments first), so those are
+    /// best checked for before they get here.
+    pub fn segment(mut self, value: impl Display) -> Self {
+        self.url.push('/');
+        self.url
+            .extend(utf8_percent_encode(value.to_string().as_str(), SEGMENT));
+        self
+    }
+
+    /// Returns the finished URL.
+    #[must_use]
+    pub fn build(self) -> String {
+        self.url
+    }
+}
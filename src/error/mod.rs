@@ -1,3 +1,6 @@
+use crate::challenge::Challenge;
+use reqwest::header::HeaderMap;
+use reqwest::Method;
 use std::fmt::{Display, Formatter};
 use thiserror::Error;
 
@@ -13,6 +16,15 @@ pub enum ClientError {
     CookieError(String),
     /// The reqwest client builder failed.
     ReqwestBuildError(String),
+    /// The two-step verification secret is invalid.
+    InvalidTotpSecret(String),
+    /// The two-step verification code settings (digits or period) are invalid.
+    InvalidTotpSettings(String),
+    /// The operating system couldn't generate random bytes.
+    RandomnessError(String),
+    /// A verification phrase was asked for with fewer than
+    /// [`MIN_PHRASE_WORDS`](crate::verification::MIN_PHRASE_WORDS) words. Contains the number asked for.
+    PhraseTooShort(usize),
 }
 
 /// Represents an error-type response from a request.
@@ -24,16 +36,241 @@ pub enum RequestError {
     NotAuthenticated,
     /// Automatic client reauthentication failed
     ReauthenticationFailed(String),
-    /// The `.ROBLOSECURITY` cookie in use expired
-    ExpiredCookie,
+    /// The `.ROBLOSECURITY` cookie in use expired (the server returned a 401
+    /// to a request sent with it)
+    ExpiredCookie(Box<HttpError>),
     /// There was an error sending the request
     RequestError(String, String),
-    /// The server returned a 400-class error code (client error).
-    /// Contains the url, status code, and a collection of errors
-    ClientError(String, u16, RobloxApiErrors),
+    /// The server returned a 401 to a request that wasn't sent with the
+    /// `.ROBLOSECURITY` cookie (i.e. the API key is invalid)
+    Unauthorized(Box<HttpError>),
+    /// The server returned a 403 because the account lacks a permission
+    /// (i.e. it can't manage members of that group). This is decided by the error
+    /// code, using the codes each endpoint documents for it (see
+    /// [`Endpoint::permission_error_codes`](crate::endpoint::Endpoint::permission_error_codes))
+    InsufficientPermissions(Box<HttpError>),
+    /// The server returned a 403 for any other reason
+    Forbidden(Box<HttpError>),
+    /// The server returned any other 400-class error code (client error),
+    /// or a status code that isn't an error or a success
+    ClientError(Box<HttpError>),
     /// The server returned a 500-class error code (server error)
-    /// Contains the status code
-    ServerError(u16),
+    ServerError(Box<HttpError>),
+    /// A username or display name failed client-side validation, so
+    /// no request was sent
+    InvalidName(NameValidationError),
+    /// The server wants a challenge solved before accepting the request, and no
+    /// registered [`ChallengeSolver`](crate::challenge::ChallengeSolver) solved it.
+    /// Contains the url and the challenge
+    ChallengeRequired(String, Challenge),
+    /// A [`ChallengeSolver`](crate::challenge::ChallengeSolver) took on a challenge but
+    /// couldn't complete it. Contains the challenge and the reason
+    ChallengeFailed(Challenge, String),
+    /// Roblox won't (or wouldn't) change the authenticated user's username
+    UsernameChange(UsernameChangeError),
+    /// The server responded successfully, but the body didn't have the expected shape
+    /// (usually because Roblox changed it)
+    Deserialize(Box<DeserializeError>),
+}
+
+/// Everything known about a failed HTTP request. Carried by every [`RequestError`]
+/// variant that comes from an error response.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct HttpError {
+    /// The method the request was sent with.
+    pub method: Method,
+    /// The URL the request was sent to.
+    pub url: String,
+    /// The status code of the response.
+    pub status: u16,
+    /// The errors in the response body. Empty if the body isn't in Roblox's usual
+    /// `{ "errors": [...] }` shape.
+    pub errors: RobloxApiErrors,
+    /// The raw response body.
+    pub body: String,
+    /// The response headers, minus any `set-cookie` headers.
+    pub headers: HeaderMap,
+}
+
+impl HttpError {
+    /// Returns the code of the first error in the response body, if there is one.
+    #[must_use]
+    pub fn code(&self) -> Option<i16> {
+        self.errors.errors.first().map(|error| error.code)
+    }
+}
+
+impl Display for HttpError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} {} returned status code {}",
+            self.method, self.url, self.status
+        )?;
+        if self.errors.errors.is_empty() {
+            write!(f, "Response body:\n{}", self.body)
+        } else {
+            write!(f, "{}", self.errors)
+        }
+    }
+}
+
+/// Where and why a response body couldn't be parsed.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct DeserializeError {
+    /// The URL the response came from.
+    pub url: String,
+    /// The path to the value that failed to parse (i.e. `data[3].role.memberCount`),
+    /// or `.` if the whole body was the problem.
+    pub path: String,
+    /// The type the body was being parsed into.
+    pub target: &'static str,
+    /// What went wrong, including the type that was expected at `path`.
+    pub message: String,
+    /// The start of the raw body. Bodies longer than [`DeserializeError::BODY_LIMIT`]
+    /// bytes are cut off, and the values of [`DeserializeError::SECRET_FIELDS`] are
+    /// replaced with `[REDACTED]`.
+    pub body: String,
+}
+
+impl DeserializeError {
+    /// How much of the body is kept, in bytes.
+    pub const BODY_LIMIT: usize = 1024;
+
+    /// Fields whose values are never kept, since they hold live credentials (i.e. the
+    /// tokens in an OAuth token response).
+    pub const SECRET_FIELDS: [&'static str; 3] = ["access_token", "refresh_token", "id_token"];
+
+    /// Parses `body` into a `T`, describing the failure if it doesn't fit.
+    pub(crate) fn parse<T>(url: &str, body: &str) -> Result<T, Box<Self>>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let deserializer = &mut serde_json::Deserializer::from_str(body);
+        serde_path_to_error::deserialize(deserializer).map_err(|e| {
+            let body = Self::redact(body);
+            let mut end = body.len().min(Self::BODY_LIMIT);
+            while !body.is_char_boundary(end) {
+                end -= 1;
+            }
+            let mut truncated = body[..end].to_string();
+            if end < body.len() {
+                truncated.push_str("...");
+            }
+
+            Box::new(Self {
+                url: url.to_string(),
+                path: e.path().to_string(),
+                target: std::any::type_name::<T>(),
+                message: e.into_inner().to_string(),
+                body: truncated,
+            })
+        })
+    }
+
+    /// Replaces the values of [`SECRET_FIELDS`](DeserializeError::SECRET_FIELDS) in `body`.
+    /// A body that mentions one but isn't valid JSON is left out entirely.
+    fn redact(body: &str) -> String {
+        // Reserializing reorders keys, so bodies without secrets are kept as they were
+        if !Self::SECRET_FIELDS.iter().any(|field| body.contains(field)) {
+            return body.to_string();
+        }
+
+        fn redact_value(value: &mut serde_json::Value) {
+            match value {
+                serde_json::Value::Object(map) => {
+                    for (key, value) in map.iter_mut() {
+                        if DeserializeError::SECRET_FIELDS.contains(&key.as_str()) {
+                            *value = serde_json::Value::from("[REDACTED]");
+                        } else {
+                            redact_value(value);
+                        }
+                    }
+                }
+                serde_json::Value::Array(values) => values.iter_mut().for_each(redact_value),
+                _ => {}
+            }
+        }
+
+        match serde_json::from_str::<serde_json::Value>(body) {
+            Ok(mut value) => {
+                redact_value(&mut value);
+                value.to_string()
+            }
+            Err(_) => "(left out, since it may contain tokens)".to_string(),
+        }
+    }
+}
+
+impl Display for DeserializeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Couldn't parse the response from {} as {} at `{}`: {}\nResponse body:\n{}",
+            self.url, self.target, self.path, self.message, self.body
+        )
+    }
+}
+
+impl RequestError {
+    /// Returns the failed response this error came from, if it came from one.
+    #[must_use]
+    pub fn http_error(&self) -> Option<&HttpError> {
+        match self {
+            Self::ExpiredCookie(error)
+            | Self::Unauthorized(error)
+            | Self::InsufficientPermissions(error)
+            | Self::Forbidden(error)
+            | Self::ClientError(error)
+            | Self::ServerError(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Returns the status code of the failed response this error came from, if it
+    /// came from one.
+    #[must_use]
+    pub fn status(&self) -> Option<u16> {
+        self.http_error().map(|error| error.status)
+    }
+}
+
+/// Represents a reason that a username change didn't go through.
+/// Returned by [`change_username`](crate::client::RustbloxClient::change_username).
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UsernameChangeError {
+    /// Someone else already has the username.
+    Taken,
+    /// The username isn't appropriate for Roblox.
+    Moderated,
+    /// The username breaks one of Roblox's rules. Contains Roblox's explanation.
+    Invalid(String),
+    /// The password was wrong.
+    IncorrectPassword,
+    /// The account doesn't have enough Robux.
+    InsufficientRobux { cost: u64, balance: u64 },
+}
+
+/// Represents a reason that a username or display name is invalid.
+/// Returned by the functions in [`validation`](crate::validation).
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NameValidationError {
+    /// The name is too short. Contains the name's length.
+    TooShort(usize),
+    /// The name is too long. Contains the name's length.
+    TooLong(usize),
+    /// The name contains a character that isn't allowed.
+    InvalidCharacter(char),
+    /// The name contains more than one underscore.
+    TooManyUnderscores,
+    /// The name starts or ends with an underscore.
+    LeadingOrTrailingUnderscore,
+    /// The name starts or ends with a space.
+    LeadingOrTrailingSpace,
 }
 
 impl Display for ClientError {
@@ -50,6 +287,22 @@ impl Display for ClientError {
             Self::ReqwestBuildError(err) => {
                 f.write_str(format!("Had an error building the Reqwest client:\n{err}").as_str())
             }
+            Self::InvalidTotpSecret(err) => {
+                f.write_str(format!("The two-step verification secret is invalid:\n{err}").as_str())
+            }
+            Self::InvalidTotpSettings(err) => f.write_str(
+                format!("The two-step verification code settings are invalid:\n{err}").as_str(),
+            ),
+            Self::RandomnessError(err) => {
+                f.write_str(format!("Couldn't generate random bytes:\n{err}").as_str())
+            }
+            Self::PhraseTooShort(words) => f.write_str(
+                format!(
+                    "A verification phrase of {words} words is too easy to match by accident; it needs at least {}",
+                    crate::verification::MIN_PHRASE_WORDS
+                )
+                .as_str(),
+            ),
         }
     }
 }
@@ -61,20 +314,83 @@ impl Display for RequestError {
             Self::ReauthenticationFailed(msg) => {
                 f.write_str(format!("Automatic reauthentication failed:\n{msg}").as_str())
             }
-            Self::ExpiredCookie => f.write_str("The .ROBLOSECURITY cookie expired."),
+            Self::ExpiredCookie(error) => {
+                f.write_str(format!("The .ROBLOSECURITY cookie expired.\n{error}").as_str())
+            }
             Self::RequestError(url, err) => {
                 f.write_str(format!("Had an error sending the request to {url}:\n{err}").as_str())
             }
-            Self::ClientError(url, status_code, errors) => f.write_str(
+            Self::Unauthorized(error) => {
+                f.write_str(format!("The credentials were rejected.\n{error}").as_str())
+            }
+            Self::InsufficientPermissions(error) => f.write_str(
+                format!("The account doesn't have permission to do this.\n{error}").as_str(),
+            ),
+            Self::Forbidden(error) => f.write_str(format!("Access was denied.\n{error}").as_str()),
+            Self::ClientError(error) | Self::ServerError(error) => {
+                f.write_str(error.to_string().as_str())
+            }
+            Self::InvalidName(err) => f.write_str(format!("Invalid name: {err}").as_str()),
+            Self::UsernameChange(err) => {
+                f.write_str(format!("Couldn't change the username: {err}").as_str())
+            }
+            Self::ChallengeRequired(url, challenge) => f.write_str(
+                format!(
+                    "{url} requires a {} challenge to be solved (challenge ID {})",
+                    challenge.challenge_type.as_str(),
+                    challenge.id
+                )
+                .as_str(),
+            ),
+            Self::ChallengeFailed(challenge, reason) => f.write_str(
+                format!(
+                    "Couldn't solve the {} challenge (challenge ID {}):\n{reason}",
+                    challenge.challenge_type.as_str(),
+                    challenge.id
+                )
+                .as_str(),
+            ),
+            Self::Deserialize(error) => f.write_str(error.to_string().as_str()),
+        }
+    }
+}
+
+impl Display for UsernameChangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Taken => f.write_str("The username is taken"),
+            Self::Moderated => f.write_str("The username isn't appropriate for Roblox"),
+            Self::Invalid(reason) => {
+                f.write_str(format!("The username is invalid ({reason})").as_str())
+            }
+            Self::IncorrectPassword => f.write_str("The password is incorrect"),
+            Self::InsufficientRobux { cost, balance } => f.write_str(
                 format!(
-                    "{url} returned status code {status_code}\n{}",
-                    errors.to_string()
+                    "Changing the username costs {cost} Robux, but the account only has {balance}"
                 )
                 .as_str(),
             ),
-            Self::ServerError(status_code) => {
-                f.write_str(format!("Server returned status code {status_code}").as_str())
+        }
+    }
+}
+
+impl Display for NameValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooShort(length) => f.write_str(
+                format!("The name is too short ({length} characters, minimum is 3)").as_str(),
+            ),
+            Self::TooLong(length) => f.write_str(
+                format!("The name is too long ({length} characters, maximum is 20)").as_str(),
+            ),
+            Self::InvalidCharacter(c) => {
+                f.write_str(format!("The name contains an invalid character ({c:?})").as_str())
+            }
+            Self::TooManyUnderscores => f.write_str("The name contains more than one underscore"),
+            Self::LeadingOrTrailingUnderscore => {
+                f.write_str("The name can't start or end with an underscore")
             }
+            Self::LeadingOrTrailingSpace => f.write_str("The name can't start or end with a space"),
         }
     }
 }
@@ -85,25 +401,23 @@ pub struct RobloxApiError {
     pub message: String,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq)]
+#[derive(Deserialize, Debug, Default, Clone, Eq, PartialEq)]
 pub struct RobloxApiErrors {
     pub errors: Vec<RobloxApiError>,
 }
 
-impl ToString for RobloxApiError {
-    fn to_string(&self) -> String {
-        format!("Error code {}: \"{}\"", self.code, self.message)
+impl Display for RobloxApiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Error code {}: \"{}\"", self.code, self.message)
     }
 }
 
-impl ToString for RobloxApiErrors {
-    fn to_string(&self) -> String {
-        let mut format_str = String::from("Roblox API Errors:\n");
+impl Display for RobloxApiErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Roblox API Errors:\n")?;
         for error in &self.errors {
-            format_str += " - ";
-            format_str += error.to_string().as_str();
-            format_str += "\n";
+            writeln!(f, " - {error}")?;
         }
-        format_str
+        Ok(())
     }
 }
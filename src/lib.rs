@@ -1,13 +1,21 @@
 #[macro_use]
 extern crate serde;
 #[macro_use]
-extern crate serde_json;
-#[macro_use]
 extern crate log;
 
+pub mod audit;
+pub mod challenge;
 pub mod client;
+pub mod credentials;
+pub mod drift;
+pub mod endpoint;
 pub mod error;
+pub mod oauth;
+pub mod presence;
 pub mod structs;
+pub mod twostepverification;
+pub mod validation;
+pub mod verification;
 
 mod routes;
 
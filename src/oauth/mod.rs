@@ -0,0 +1,267 @@
+//! OAuth 2.0 support for apps that act on behalf of Roblox users.
+//!
+//! The flow goes like this:
+//! 1. Send the user to the URL from [`OAuthApp::authorization_url`], keeping the
+//!    returned [`PkceVerifier`] (and your `state`) around until they come back.
+//! 2. Roblox redirects the user to your redirect URI with a `code`. Trade it for tokens with
+//!    [`exchange_oauth_code`](RustbloxClient::exchange_oauth_code).
+//! 3. Wrap the tokens in an [`OAuthSession`] and hand it to
+//!    [`RustbloxClientBuilder::oauth`](crate::client::builder::RustbloxClientBuilder::oauth).
+//!    Requests that use [`AuthMethod::OAuth`](crate::client::AuthMethod::OAuth) are then sent
+//!    with the access token, which is refreshed whenever it is about to expire.
+
+use crate::client::RustbloxClient;
+use crate::error::{ClientError, RequestError};
+use crate::structs::oauth::OAuthToken;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+const AUTHORIZE_URL: &str = "https://apis.roblox.com/oauth/v1/authorize";
+
+/// How long before an access token expires that it gets refreshed.
+const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// An OAuth 2.0 app registered with Roblox.
+#[derive(Clone, PartialEq, Eq)]
+pub struct OAuthApp {
+    /// The app's client ID.
+    pub client_id: String,
+    /// The app's client secret. Public apps (i.e. desktop or mobile apps) that
+    /// can't keep a secret rely on PKCE alone and leave this as `None`.
+    pub client_secret: Option<String>,
+    /// Where Roblox sends users after they authorize the app. Must match one
+    /// of the redirect URIs registered for the app.
+    pub redirect_uri: String,
+}
+
+impl Debug for OAuthApp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Don't leak the secret into logs
+        f.debug_struct("OAuthApp")
+            .field("client_id", &self.client_id)
+            .field("redirect_uri", &self.redirect_uri)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OAuthApp {
+    /// Creates a public app (one without a client secret).
+    #[must_use]
+    pub fn new(client_id: &str, redirect_uri: &str) -> Self {
+        Self {
+            client_id: client_id.to_string(),
+            client_secret: None,
+            redirect_uri: redirect_uri.to_string(),
+        }
+    }
+
+    /// Sets the app's client secret.
+    #[must_use]
+    pub fn client_secret(mut self, client_secret: &str) -> Self {
+        self.client_secret = Some(client_secret.to_string());
+        self
+    }
+
+    /// Builds the URL to send users to so they can authorize the app for `scopes`
+    /// (i.e. `["openid", "profile"]`). A fresh [`PkceVerifier`] is generated, which has to
+    /// be passed to [`exchange_oauth_code`](RustbloxClient::exchange_oauth_code) later.
+    ///
+    /// `state` is sent back with the redirect unchanged. It should be unguessable and
+    /// checked when the user comes back, to protect against cross-site request forgery.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the operating system can't generate random bytes.
+    pub fn authorization_url(
+        &self,
+        scopes: &[&str],
+        state: &str,
+    ) -> Result<(String, PkceVerifier), ClientError> {
+        let verifier = PkceVerifier::new()?;
+        Ok((
+            self.authorization_url_with(scopes, state, &verifier),
+            verifier,
+        ))
+    }
+
+    /// Builds the authorization URL for an existing `verifier`.
+    #[must_use]
+    pub fn authorization_url_with(
+        &self,
+        scopes: &[&str],
+        state: &str,
+        verifier: &PkceVerifier,
+    ) -> String {
+        let mut url = reqwest::Url::parse(AUTHORIZE_URL).expect("AUTHORIZE_URL is a valid URL");
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("redirect_uri", &self.redirect_uri)
+            .append_pair("scope", &scopes.join(" "))
+            .append_pair("response_type", "code")
+            .append_pair("state", state)
+            .append_pair("code_challenge", &verifier.challenge())
+            .append_pair("code_challenge_method", "S256");
+        url.to_string()
+    }
+}
+
+/// A PKCE code verifier (RFC 7636). Proves that whoever exchanges an authorization
+/// code is whoever asked for it.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct PkceVerifier(String);
+
+impl Debug for PkceVerifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("PkceVerifier").finish_non_exhaustive()
+    }
+}
+
+impl PkceVerifier {
+    /// Generates a verifier from 32 random bytes.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the operating system can't generate random bytes.
+    pub fn new() -> Result<Self, ClientError> {
+        let mut bytes = [0_u8; 32];
+        getrandom::getrandom(&mut bytes)
+            .map_err(|e| ClientError::RandomnessError(e.to_string()))?;
+        Ok(Self(URL_SAFE_NO_PAD.encode(bytes)))
+    }
+
+    /// Uses an existing verifier, i.e. one that was stored while the user was away.
+    #[must_use]
+    pub fn from_string(verifier: String) -> Self {
+        Self(verifier)
+    }
+
+    /// Returns the verifier.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Returns the `S256` code challenge for this verifier.
+    #[must_use]
+    pub fn challenge(&self) -> String {
+        URL_SAFE_NO_PAD.encode(Sha256::digest(self.0.as_bytes()))
+    }
+}
+
+/// Called with the new tokens after every refresh.
+type RefreshCallback = Arc<dyn Fn(&OAuthToken) + Send + Sync>;
+
+struct SessionState {
+    token: OAuthToken,
+    expires_at: Instant,
+}
+
+impl SessionState {
+    fn new(token: OAuthToken) -> Self {
+        let expires_at = Instant::now() + Duration::from_secs(token.expires_in);
+        Self { token, expires_at }
+    }
+}
+
+/// A set of OAuth 2.0 tokens that keeps itself fresh. Used as a client's
+/// credentials with
+/// [`RustbloxClientBuilder::oauth`](crate::client::builder::RustbloxClientBuilder::oauth).
+///
+/// Clones share the same tokens, and only one refresh happens at a time, so a
+/// rotated refresh token is never used twice.
+#[derive(Clone)]
+pub struct OAuthSession {
+    app: OAuthApp,
+    state: Arc<Mutex<SessionState>>,
+    on_refresh: Option<RefreshCallback>,
+}
+
+impl Debug for OAuthSession {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OAuthSession")
+            .field("app", &self.app)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OAuthSession {
+    /// Creates a session for `app` from a set of tokens. The access token is assumed
+    /// to have been issued just now; if it's older than that, the first request
+    /// may need a retry after the token is refreshed.
+    #[must_use]
+    pub fn new(app: OAuthApp, token: OAuthToken) -> Self {
+        Self {
+            app,
+            state: Arc::new(Mutex::new(SessionState::new(token))),
+            on_refresh: None,
+        }
+    }
+
+    /// Sets a function that is called with the new tokens every time they are refreshed.
+    /// Since refresh tokens are rotated, this is the place to persist them.
+    #[must_use]
+    pub fn on_refresh(mut self, callback: impl Fn(&OAuthToken) + Send + Sync + 'static) -> Self {
+        self.on_refresh = Some(Arc::new(callback));
+        self
+    }
+
+    /// Returns the app this session belongs to.
+    #[must_use]
+    pub fn app(&self) -> &OAuthApp {
+        &self.app
+    }
+
+    /// Returns the current set of tokens.
+    pub async fn token(&self) -> OAuthToken {
+        self.state.lock().await.token.clone()
+    }
+
+    /// Returns an access token, refreshing it first if it is about to expire.
+    pub(crate) async fn access_token(
+        &self,
+        client: &RustbloxClient,
+    ) -> Result<String, RequestError> {
+        let mut state = self.state.lock().await;
+        if state.expires_at <= Instant::now() + REFRESH_MARGIN {
+            self.refresh(client, &mut state).await?;
+        }
+        Ok(state.token.access_token.clone())
+    }
+
+    /// Refreshes the tokens after Roblox rejected `stale_token`. Nothing happens if
+    /// another request already replaced it in the meantime.
+    pub(crate) async fn refresh_rejected(
+        &self,
+        client: &RustbloxClient,
+        stale_token: &str,
+    ) -> Result<(), RequestError> {
+        let mut state = self.state.lock().await;
+        if state.token.access_token == stale_token {
+            self.refresh(client, &mut state).await?;
+        }
+        Ok(())
+    }
+
+    async fn refresh(
+        &self,
+        client: &RustbloxClient,
+        state: &mut SessionState,
+    ) -> Result<(), RequestError> {
+        debug!("Refreshing the OAuth access token");
+        let token = client
+            .refresh_oauth_token(&self.app, state.token.refresh_token.clone())
+            .await
+            .map_err(|e| RequestError::ReauthenticationFailed(e.to_string()))?;
+
+        if let Some(callback) = &self.on_refresh {
+            callback(&token);
+        }
+        *state = SessionState::new(token);
+        Ok(())
+    }
+}
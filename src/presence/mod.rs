@@ -0,0 +1,160 @@
+//! Keeping an eye on when users come online, go offline or change games.
+//!
+//! A [`PresenceWatcher`] polls the presences of a set of users and sends a
+//! [`PresenceEvent`] down a channel whenever one of them changes:
+//!
+//! ```no_run
+//! # async fn example(client: rustblox::client::RustbloxClient) {
+//! use rustblox::presence::{PresenceEvent, PresenceWatcher};
+//! use std::time::Duration;
+//!
+//! let (watcher, mut events) = PresenceWatcher::new(client, [156, 261], Duration::from_secs(30));
+//! tokio::spawn(watcher.run());
+//!
+//! while let Some(event) = events.recv().await {
+//!     match event {
+//!         PresenceEvent::Changed { current, .. } => {
+//!             println!("{} is now {:?}", current.user_id, current.user_presence_type)
+//!         }
+//!         PresenceEvent::Error(why) => println!("Couldn't get presences: {why}"),
+//!         _ => {}
+//!     }
+//! }
+//! # }
+//! ```
+
+use crate::client::RustbloxClient;
+use crate::error::RequestError;
+use crate::structs::presence::UserPresence;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+/// Something a [`PresenceWatcher`] noticed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PresenceEvent {
+    /// A watched user's presence changed. `previous` is None the first time the
+    /// user is seen, so the first poll reports everyone's starting presence.
+    Changed {
+        previous: Option<UserPresence>,
+        current: UserPresence,
+    },
+    /// A poll failed. [`run`](PresenceWatcher::run) keeps going and tries again
+    /// after the usual interval.
+    Error(RequestError),
+}
+
+/// Polls the presences of a set of users and reports changes. Users are looked up in
+/// batches (see [`get_user_presences`](RustbloxClient::get_user_presences)), so watching
+/// many users only takes a few requests per poll.
+///
+/// Changes to the last online time alone aren't reported; see
+/// [`UserPresence::same_activity`].
+pub struct PresenceWatcher {
+    client: RustbloxClient,
+    user_ids: Vec<usize>,
+    interval: Duration,
+    presences: HashMap<usize, UserPresence>,
+    sender: UnboundedSender<PresenceEvent>,
+}
+
+impl PresenceWatcher {
+    /// Creates a watcher for `user_ids` that polls every `interval` once it's
+    /// [run](PresenceWatcher::run). Events are sent to the returned receiver.
+    pub fn new(
+        client: RustbloxClient,
+        user_ids: impl IntoIterator<Item = usize>,
+        interval: Duration,
+    ) -> (Self, UnboundedReceiver<PresenceEvent>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let mut watcher = Self {
+            client,
+            user_ids: Vec::new(),
+            interval,
+            presences: HashMap::new(),
+            sender,
+        };
+        for user_id in user_ids {
+            watcher.watch(user_id);
+        }
+        (watcher, receiver)
+    }
+
+    /// Starts watching `user_id`, if it isn't watched already.
+    pub fn watch(&mut self, user_id: usize) {
+        if !self.user_ids.contains(&user_id) {
+            self.user_ids.push(user_id);
+        }
+    }
+
+    /// Stops watching `user_id` and forgets its last known presence.
+    pub fn unwatch(&mut self, user_id: usize) {
+        self.user_ids.retain(|id| *id != user_id);
+        self.presences.remove(&user_id);
+    }
+
+    /// Returns the IDs of the users being watched.
+    #[must_use]
+    pub fn watched(&self) -> &[usize] {
+        &self.user_ids
+    }
+
+    /// Returns the last known presence of `user_id`, if it has been polled yet.
+    #[must_use]
+    pub fn presence(&self, user_id: usize) -> Option<&UserPresence> {
+        self.presences.get(&user_id)
+    }
+
+    /// Polls every watched user once and sends an event for each one whose presence
+    /// changed. Returns how many events were sent.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if any of the requests could not be made, or if the endpoint responded with an error.
+    /// No events are sent in that case.
+    pub async fn poll(&mut self) -> Result<usize, RequestError> {
+        let presences = self.client.get_user_presences(&self.user_ids).await?;
+        Ok(self.update(presences))
+    }
+
+    /// Polls forever, waiting `interval` between polls. Failed polls are sent as
+    /// [`PresenceEvent::Error`]. This stops once the receiver is dropped, so it can be
+    /// handed straight to `tokio::spawn`.
+    pub async fn run(mut self) {
+        while !self.sender.is_closed() {
+            if let Err(why) = self.poll().await {
+                // Nobody is listening anymore if this fails, which the loop notices
+                let _ = self.sender.send(PresenceEvent::Error(why));
+            }
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    /// Records fresh presences and sends an event for each one that differs from the last.
+    pub(crate) fn update(&mut self, presences: Vec<UserPresence>) -> usize {
+        let mut sent = 0;
+        for current in presences {
+            // Roblox shouldn't send anyone that wasn't asked for, but just in case
+            if !self.user_ids.contains(&current.user_id) {
+                continue;
+            }
+
+            // Stored even when unchanged, so the last online time stays up to date
+            let previous = self.presences.insert(current.user_id, current.clone());
+            let changed = match &previous {
+                Some(previous) => !previous.same_activity(&current),
+                None => true,
+            };
+            if changed
+                && self
+                    .sender
+                    .send(PresenceEvent::Changed { previous, current })
+                    .is_ok()
+            {
+                sent += 1;
+            }
+        }
+        sent
+    }
+}
@@ -0,0 +1,276 @@
+use crate::client::{AuthMethod, RustbloxClient};
+use crate::endpoint::{Endpoint, UrlBuilder};
+use crate::error::{RequestError, UsernameChangeError};
+use crate::structs::auth::{
+    ExistingUsernames, UsernameChangeQuote, UsernameContext, UsernameStatus, UsernameValidation,
+    USERNAME_CHANGE_COST,
+};
+use crate::validation::validate_username;
+use reqwest::Method;
+use std::fmt::{Debug, Formatter};
+
+const BASE_URL: &str = "https://auth.roblox.com";
+
+/// The code [`ChangeUsername`] answers a wrong password with (status 403).
+const INCORRECT_PASSWORD_CODE: i16 = 3;
+
+/// Asks Roblox whether a username can be used.
+/// Used in [`validate_username_for_change`](RustbloxClient::validate_username_for_change).
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ValidateUsername {
+    pub username: String,
+    pub context: UsernameContext,
+}
+
+impl Endpoint for ValidateUsername {
+    type Body = Self;
+    type Response = UsernameValidation;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("v1/usernames/validate")
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    // Only checks the username
+    fn mutating(&self) -> bool {
+        false
+    }
+}
+
+/// Looks up existing usernames that match a username.
+/// Used in [`is_username_available`](RustbloxClient::is_username_available).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetExistingUsernames {
+    pub username: String,
+}
+
+impl Endpoint for GetExistingUsernames {
+    type Body = ();
+    type Response = ExistingUsernames;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL).path("v1/usernames").build()
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        vec![("username", self.username.clone())]
+    }
+}
+
+/// Changes the authenticated user's username. Roblox wants the account's password
+/// for this, and charges [`USERNAME_CHANGE_COST`] Robux.
+/// Used in [`change_username`](RustbloxClient::change_username).
+#[derive(Serialize, Clone, PartialEq, Eq)]
+pub struct ChangeUsername {
+    pub username: String,
+    pub password: String,
+}
+
+// The password stays out of logs
+impl Debug for ChangeUsername {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChangeUsername")
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Endpoint for ChangeUsername {
+    type Body = Self;
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL).path("v2/username").build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn audit_targets(&self) -> Vec<(&'static str, serde_json::Value)> {
+        vec![("new_username", self.username.clone().into())]
+    }
+}
+
+impl RustbloxClient {
+    /// **MUST AUTHENTICATE**
+    ///
+    /// Asks Roblox whether the authenticated user could change their username to `username`.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - You do not have a `.ROBLOSECURITY` cookie set
+    /// - The endpoint responds with an error
+    /// - Client-side validation is enabled and `username` is not a valid username.
+    ///   No request is sent in this case.
+    pub async fn validate_username_for_change(
+        &self,
+        username: &str,
+    ) -> Result<UsernameStatus, RequestError> {
+        if self.validate_names {
+            validate_username(username).map_err(RequestError::InvalidName)?;
+        }
+
+        let validation = self
+            .execute(ValidateUsername {
+                username: username.to_string(),
+                context: UsernameContext::UsernameChange,
+            })
+            .await?;
+        Ok(validation.status())
+    }
+
+    /// Checks whether nobody has `username` yet. This doesn't check Roblox's other
+    /// username rules; use [`validate_username_for_change`](RustbloxClient::validate_username_for_change)
+    /// for that.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the request could not be made, or if the endpoint responded with an error.
+    pub async fn is_username_available(&self, username: &str) -> Result<bool, RequestError> {
+        let existing = self
+            .execute(GetExistingUsernames {
+                username: username.to_string(),
+            })
+            .await?;
+        Ok(!existing
+            .usernames
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(username)))
+    }
+
+    /// **MUST AUTHENTICATE**
+    ///
+    /// Finds out whether the authenticated user can change their username to `username`,
+    /// and what it would cost. Nothing is changed; follow up with
+    /// [`change_username`](RustbloxClient::change_username) and the account's password.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - You do not have a `.ROBLOSECURITY` cookie set
+    /// - Any of the endpoints respond with an error
+    /// - Client-side validation is enabled and `username` is not a valid username.
+    ///   No request is sent in this case.
+    pub async fn prepare_username_change(
+        &self,
+        username: &str,
+    ) -> Result<UsernameChangeQuote, RequestError> {
+        let status = self.validate_username_for_change(username).await?;
+        let user = self.get_authenticated_user().await?;
+        let balance = self.get_user_robux(user.id).await?;
+
+        Ok(UsernameChangeQuote {
+            current_username: user.name,
+            username: username.to_string(),
+            status,
+            cost: USERNAME_CHANGE_COST,
+            balance,
+        })
+    }
+
+    /// **MUST AUTHENTICATE**
+    ///
+    /// Changes the authenticated user's username to `username`, confirming with the
+    /// account's `password`. This costs [`USERNAME_CHANGE_COST`] Robux.
+    ///
+    /// The username and the account's balance are checked first (see
+    /// [`prepare_username_change`](RustbloxClient::prepare_username_change)), so a change that
+    /// can't go through is stopped before the password is sent.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - You do not have a `.ROBLOSECURITY` cookie set
+    /// - Any of the endpoints respond with an error
+    /// - Client-side validation is enabled and `username` is not a valid username.
+    ///   No request is sent in this case.
+    /// - The username is taken, moderated or invalid, the password is wrong, or the
+    ///   account can't afford the change. These return [`RequestError::UsernameChange`].
+    ///
+    /// Possible error responses include:
+    /// - Status 400 code 1: A valid username is required
+    /// - Status 401 code 0: Authorization denied
+    /// - Status 403 code 0: Token validation failed
+    /// - Status 403 code 2: You don't have enough Robux to change your username
+    /// - Status 403 code 3: Your password is incorrect
+    pub async fn change_username(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> Result<(), RequestError> {
+        let quote = self.prepare_username_change(username).await?;
+        match quote.status {
+            UsernameStatus::Valid => {}
+            UsernameStatus::Taken => {
+                return Err(RequestError::UsernameChange(UsernameChangeError::Taken))
+            }
+            UsernameStatus::Moderated => {
+                return Err(RequestError::UsernameChange(UsernameChangeError::Moderated))
+            }
+            UsernameStatus::Invalid(reason) => {
+                return Err(RequestError::UsernameChange(UsernameChangeError::Invalid(
+                    reason,
+                )))
+            }
+        }
+        if quote.balance < quote.cost {
+            return Err(RequestError::UsernameChange(
+                UsernameChangeError::InsufficientRobux {
+                    cost: quote.cost,
+                    balance: quote.balance,
+                },
+            ));
+        }
+
+        let previous_name = serde_json::json!({ "username": quote.current_username });
+        let result = self
+            .execute_with_previous_state(
+                ChangeUsername {
+                    username: username.to_string(),
+                    password: password.to_string(),
+                },
+                Some(previous_name),
+            )
+            .await;
+
+        result.map(|_| ()).map_err(username_change_error)
+    }
+}
+
+/// Turns [`ChangeUsername`]'s answer to a wrong password into
+/// [`UsernameChangeError::IncorrectPassword`]. Other errors are left alone.
+pub(crate) fn username_change_error(error: RequestError) -> RequestError {
+    match error {
+        RequestError::Forbidden(error) if error.code() == Some(INCORRECT_PASSWORD_CODE) => {
+            RequestError::UsernameChange(UsernameChangeError::IncorrectPassword)
+        }
+        other => other,
+    }
+}
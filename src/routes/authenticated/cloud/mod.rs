@@ -0,0 +1,66 @@
+use crate::client::RustbloxClient;
+use crate::endpoint::{Endpoint, UrlBuilder};
+use crate::error::RequestError;
+use crate::structs::cloud::ApiKeyInfo;
+use reqwest::Method;
+
+const BASE_URL: &str = "https://apis.roblox.com/api-keys/v1";
+
+/// Gets information about an Open Cloud API key, such as its scopes and
+/// whether it has expired.
+/// Used in [`introspect_api_key`](RustbloxClient::introspect_api_key).
+#[derive(Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct IntrospectApiKey {
+    pub api_key: String,
+}
+
+impl std::fmt::Debug for IntrospectApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Don't leak the key into logs
+        f.debug_struct("IntrospectApiKey").finish_non_exhaustive()
+    }
+}
+
+impl Endpoint for IntrospectApiKey {
+    type Body = Self;
+    type Response = ApiKeyInfo;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL).path("introspect").build()
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    // Only reads the key's details
+    fn mutating(&self) -> bool {
+        false
+    }
+}
+
+impl RustbloxClient {
+    /// **MUST HAVE AN API KEY**
+    ///
+    /// Gets information about the client's Open Cloud API key, such as its scopes
+    /// and expiry. Useful for checking that a key can do everything you need at startup.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - You do not have an API key set
+    /// - The endpoint responds with an error
+    pub async fn introspect_api_key(&self) -> Result<ApiKeyInfo, RequestError> {
+        let api_key = self
+            .api_key()
+            .cloned()
+            .ok_or(RequestError::NotAuthenticated)?;
+
+        self.execute(IntrospectApiKey { api_key }).await
+    }
+}
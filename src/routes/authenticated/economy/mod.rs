@@ -0,0 +1,54 @@
+use crate::client::{AuthMethod, RustbloxClient};
+use crate::endpoint::{Endpoint, UrlBuilder};
+use crate::error::RequestError;
+use crate::structs::user::UserCurrency;
+use reqwest::Method;
+
+const BASE_URL: &str = "https://economy.roblox.com/v1";
+
+/// Gets how many Robux a user has. Roblox only tells an account its own balance.
+/// Used in [`get_user_robux`](RustbloxClient::get_user_robux).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetUserCurrency {
+    pub user_id: usize,
+}
+
+impl Endpoint for GetUserCurrency {
+    type Body = ();
+    type Response = UserCurrency;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("users")
+            .segment(self.user_id)
+            .path("currency")
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+}
+
+impl RustbloxClient {
+    /// **MUST AUTHENTICATE**
+    ///
+    /// Gets how many Robux the authenticated user (`user_id`) has.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - You do not have a `.ROBLOSECURITY` cookie set
+    /// - The endpoint responds with an error
+    ///
+    /// Possible error responses include:
+    /// - Status 401 code 0: Authorization denied
+    /// - Status 403 code 0: `user_id` isn't the authenticated user
+    pub async fn get_user_robux(&self, user_id: usize) -> Result<u64, RequestError> {
+        Ok(self.execute(GetUserCurrency { user_id }).await?.robux)
+    }
+}
@@ -1,12 +1,333 @@
-use crate::client::{RequestComponents, RustbloxClient};
-use crate::error::{RequestError, RobloxApiError, RobloxApiErrors};
-use crate::structs::group::{GroupRole, JoinRequest};
+use crate::client::{AuthMethod, RustbloxClient};
+use crate::endpoint::{Endpoint, UrlBuilder};
+use crate::error::{HttpError, RequestError, RobloxApiError, RobloxApiErrors};
+use crate::structs::group::JoinRequest;
 use crate::structs::Page;
-use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::header::HeaderMap;
 use reqwest::Method;
 
 const BASE_URL: &str = "https://groups.roblox.com";
 
+/// Accepts a user's join request to a group.
+/// Used in [`accept_user_join_request`](RustbloxClient::accept_user_join_request).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceptUserJoinRequest {
+    pub group_id: usize,
+    pub user_id: usize,
+}
+
+impl Endpoint for AcceptUserJoinRequest {
+    type Body = ();
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("v1/groups")
+            .segment(self.group_id)
+            .path("join-requests/users")
+            .segment(self.user_id)
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    fn audit_targets(&self) -> Vec<(&'static str, serde_json::Value)> {
+        vec![
+            ("group_id", self.group_id.into()),
+            ("user_id", self.user_id.into()),
+        ]
+    }
+
+    // 19: You have insufficient permissions for this request.
+    fn permission_error_codes(&self) -> Vec<i16> {
+        vec![19]
+    }
+}
+
+/// Accepts a page of join requests to a group.
+/// Used in [`batch_accept_requests`](RustbloxClient::batch_accept_requests).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchAcceptRequests {
+    pub group_id: usize,
+}
+
+impl Endpoint for BatchAcceptRequests {
+    type Body = ();
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("v1/groups")
+            .segment(self.group_id)
+            .path("join-requests")
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    fn audit_targets(&self) -> Vec<(&'static str, serde_json::Value)> {
+        vec![("group_id", self.group_id.into())]
+    }
+
+    // 19: You have insufficient permissions for this request.
+    fn permission_error_codes(&self) -> Vec<i16> {
+        vec![19]
+    }
+}
+
+/// Denies a page of join requests to a group.
+/// Used in [`batch_deny_requests`](RustbloxClient::batch_deny_requests).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchDenyRequests {
+    pub group_id: usize,
+}
+
+impl Endpoint for BatchDenyRequests {
+    type Body = ();
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("v1/groups")
+            .segment(self.group_id)
+            .path("join-requests")
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    fn audit_targets(&self) -> Vec<(&'static str, serde_json::Value)> {
+        vec![("group_id", self.group_id.into())]
+    }
+
+    // 19: You have insufficient permissions for this request.
+    fn permission_error_codes(&self) -> Vec<i16> {
+        vec![19]
+    }
+}
+
+/// Gets a page of join requests to a group.
+/// Used in [`batch_get_requests`](RustbloxClient::batch_get_requests).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchGetRequests {
+    pub group_id: usize,
+}
+
+impl Endpoint for BatchGetRequests {
+    type Body = ();
+    type Response = Option<Page<JoinRequest>>;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("v1/groups")
+            .segment(self.group_id)
+            .path("join-requests")
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    // 19: You have insufficient permissions for this request.
+    fn permission_error_codes(&self) -> Vec<i16> {
+        vec![19]
+    }
+}
+
+/// Denies a user's join request to a group.
+/// Used in [`deny_user_join_request`](RustbloxClient::deny_user_join_request).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenyUserJoinRequest {
+    pub group_id: usize,
+    pub user_id: usize,
+}
+
+impl Endpoint for DenyUserJoinRequest {
+    type Body = ();
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("v1/groups")
+            .segment(self.group_id)
+            .path("join-requests/users")
+            .segment(self.user_id)
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    fn audit_targets(&self) -> Vec<(&'static str, serde_json::Value)> {
+        vec![
+            ("group_id", self.group_id.into()),
+            ("user_id", self.user_id.into()),
+        ]
+    }
+
+    // 19: You have insufficient permissions for this request.
+    fn permission_error_codes(&self) -> Vec<i16> {
+        vec![19]
+    }
+}
+
+/// Gets a user's join request to a group.
+/// Used in [`get_user_join_request`](RustbloxClient::get_user_join_request).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetUserJoinRequest {
+    pub group_id: usize,
+    pub user_id: usize,
+}
+
+impl Endpoint for GetUserJoinRequest {
+    type Body = ();
+    type Response = Option<JoinRequest>;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("v1/groups")
+            .segment(self.group_id)
+            .path("join-requests/users")
+            .segment(self.user_id)
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    // 19: You have insufficient permissions for this request.
+    fn permission_error_codes(&self) -> Vec<i16> {
+        vec![19]
+    }
+}
+
+/// Kicks (exiles) a user from a group.
+/// Used in [`kick_user`](RustbloxClient::kick_user).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KickUser {
+    pub group_id: usize,
+    pub user_id: usize,
+}
+
+impl Endpoint for KickUser {
+    type Body = ();
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("v1/groups")
+            .segment(self.group_id)
+            .path("users")
+            .segment(self.user_id)
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    fn audit_targets(&self) -> Vec<(&'static str, serde_json::Value)> {
+        vec![
+            ("group_id", self.group_id.into()),
+            ("user_id", self.user_id.into()),
+        ]
+    }
+
+    // 4: You do not have permission to manage this member.
+    fn permission_error_codes(&self) -> Vec<i16> {
+        vec![4]
+    }
+}
+
+/// Sets a user's role in a group. Note that `role_id` is the ID of the role,
+/// *not* its rank.
+/// Used in [`set_user_role_in_group`](RustbloxClient::set_user_role_in_group).
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SetUserRoleInGroup {
+    #[serde(skip)]
+    pub group_id: usize,
+    #[serde(skip)]
+    pub user_id: usize,
+    #[serde(rename = "roleId")]
+    pub role_id: usize,
+}
+
+impl Endpoint for SetUserRoleInGroup {
+    type Body = Self;
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        Method::PATCH
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("v1/groups")
+            .segment(self.group_id)
+            .path("users")
+            .segment(self.user_id)
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn audit_targets(&self) -> Vec<(&'static str, serde_json::Value)> {
+        vec![
+            ("group_id", self.group_id.into()),
+            ("user_id", self.user_id.into()),
+            ("role_id", self.role_id.into()),
+        ]
+    }
+
+    // 4: You do not have permission to manage this member.
+    fn permission_error_codes(&self) -> Vec<i16> {
+        vec![4]
+    }
+}
+
 impl RustbloxClient {
     /// **MUST AUTHENTICATE**
     ///
@@ -22,20 +343,7 @@ impl RustbloxClient {
         group_id: usize,
         user_id: usize,
     ) -> Result<(), RequestError> {
-        let url = format!("{BASE_URL}/v1/groups/{group_id}/join-requests/users/{user_id}");
-
-        let mut headers = HeaderMap::new();
-        headers.insert("Content-Length", HeaderValue::from(0));
-
-        let components = RequestComponents {
-            needs_auth: true,
-            method: Method::POST,
-            url,
-            headers: Some(headers),
-            body: None,
-        };
-
-        self.make_request::<serde_json::Value>(components, false)
+        self.execute(AcceptUserJoinRequest { group_id, user_id })
             .await?;
 
         Ok(())
@@ -51,17 +359,7 @@ impl RustbloxClient {
     /// - You do not have a `.ROBLOSECURITY` cookie set.
     /// - The endpoint responds with an error.
     pub async fn batch_accept_requests(&self, group_id: usize) -> Result<(), RequestError> {
-        let url = format!("{BASE_URL}/v1/groups/{group_id}/join-requests");
-
-        let components = RequestComponents {
-            needs_auth: true,
-            method: Method::POST,
-            url,
-            headers: None,
-            body: None,
-        };
-        self.make_request::<serde_json::Value>(components, false)
-            .await?;
+        self.execute(BatchAcceptRequests { group_id }).await?;
 
         Ok(())
     }
@@ -76,17 +374,7 @@ impl RustbloxClient {
     /// - You do not have a `.ROBLOSECURITY` cookie set.
     /// - The endpoint responds with an error.
     pub async fn batch_deny_requests(&self, group_id: usize) -> Result<(), RequestError> {
-        let url = format!("{BASE_URL}/v1/groups/{group_id}/join-requests");
-
-        let components = RequestComponents {
-            needs_auth: true,
-            method: Method::DELETE,
-            url,
-            headers: None,
-            body: None,
-        };
-        self.make_request::<serde_json::Value>(components, false)
-            .await?;
+        self.execute(BatchDenyRequests { group_id }).await?;
 
         Ok(())
     }
@@ -104,20 +392,7 @@ impl RustbloxClient {
         &self,
         group_id: usize,
     ) -> Result<Option<Page<JoinRequest>>, RequestError> {
-        let url = format!("{BASE_URL}/v1/groups/{group_id}/join-requests");
-
-        let components = RequestComponents {
-            needs_auth: true,
-            method: Method::GET,
-            url,
-            headers: None,
-            body: None,
-        };
-        let join_request_data = self
-            .make_request::<Option<Page<JoinRequest>>>(components, false)
-            .await?;
-
-        Ok(join_request_data)
+        self.execute(BatchGetRequests { group_id }).await
     }
 
     /// **MUST AUTHENTICATE**
@@ -134,17 +409,7 @@ impl RustbloxClient {
         group_id: usize,
         user_id: usize,
     ) -> Result<(), RequestError> {
-        let url = format!("{BASE_URL}/v1/groups/{group_id}/join-requests/users/{user_id}");
-
-        let components = RequestComponents {
-            needs_auth: true,
-            method: Method::DELETE,
-            url,
-            headers: None,
-            body: None,
-        };
-
-        self.make_request::<serde_json::Value>(components, false)
+        self.execute(DenyUserJoinRequest { group_id, user_id })
             .await?;
 
         Ok(())
@@ -165,20 +430,7 @@ impl RustbloxClient {
         group_id: usize,
         user_id: usize,
     ) -> Result<Option<JoinRequest>, RequestError> {
-        let url = format!("{BASE_URL}/v1/groups/{group_id}/join-requests/users/{user_id}");
-
-        let components = RequestComponents {
-            needs_auth: true,
-            method: Method::GET,
-            url,
-            headers: None,
-            body: None,
-        };
-        let join_request = self
-            .make_request::<Option<JoinRequest>>(components, false)
-            .await?;
-
-        Ok(join_request)
+        self.execute(GetUserJoinRequest { group_id, user_id }).await
     }
 
     /// **MUST AUTHENTICATE**
@@ -191,17 +443,8 @@ impl RustbloxClient {
     /// - You do not have a `.ROBLOSECURITY` cookie set.
     /// - The endpoint responds with an error.
     pub async fn kick_user(&self, group_id: usize, user_id: usize) -> Result<(), RequestError> {
-        let url = format!("{BASE_URL}/v1/groups/{group_id}/users/{user_id}");
-
-        let components = RequestComponents {
-            needs_auth: true,
-            method: Method::DELETE,
-            url,
-            headers: None,
-            body: None,
-        };
-
-        self.make_request::<serde_json::Value>(components, false)
+        let previous_role = self.audit_previous_role(group_id, user_id).await;
+        self.execute_with_previous_state(KickUser { group_id, user_id }, previous_role)
             .await?;
 
         Ok(())
@@ -224,46 +467,47 @@ impl RustbloxClient {
         user_id: usize,
         role_rank_id: u8,
     ) -> Result<(), RequestError> {
-        let url = format!("{BASE_URL}/v1/groups/{group_id}/users/{user_id}");
-
         let roles = self.get_group_roles(group_id).await?;
-        let desired_role: Vec<GroupRole> = roles
+        let desired_role = match roles
             .roles
             .into_iter()
-            .filter(|role| role.rank == role_rank_id)
-            .collect();
-        if desired_role.is_empty() {
-            let error = RobloxApiErrors {
-                errors: vec![RobloxApiError {
-                    code: 2,
-                    message: "The roleset is invalid or does not exist.".to_string(),
-                }],
-            };
-            return Err(RequestError::ClientError(url, 400, error));
-        }
-
-        let data_json = json!({
-            "roleId": desired_role.first().unwrap().id
-        });
-        let data_size = data_json.clone().to_string().as_bytes().len();
-
-        let mut headers = HeaderMap::new();
-        headers.insert("Content-Length", HeaderValue::from(data_size));
-        headers.insert(
-            "Content-Type",
-            HeaderValue::from_str("application/json").unwrap(),
-        );
-
-        let components = RequestComponents {
-            needs_auth: true,
-            method: Method::PATCH,
-            url,
-            headers: Some(headers),
-            body: Some(data_json.to_string()),
+            .find(|role| role.rank == role_rank_id)
+        {
+            Some(role) => role,
+            None => {
+                let error = RobloxApiErrors {
+                    errors: vec![RobloxApiError {
+                        code: 2,
+                        message: "The roleset is invalid or does not exist.".to_string(),
+                    }],
+                };
+                // This is what Roblox answers with when it gets a role that doesn't exist
+                return Err(RequestError::ClientError(Box::new(HttpError {
+                    method: Method::PATCH,
+                    url: UrlBuilder::new(BASE_URL)
+                        .path("v1/groups")
+                        .segment(group_id)
+                        .path("users")
+                        .segment(user_id)
+                        .build(),
+                    status: 400,
+                    body: String::new(),
+                    errors: error,
+                    headers: HeaderMap::new(),
+                })));
+            }
         };
 
-        self.make_request::<serde_json::Value>(components, false)
-            .await?;
+        let previous_role = self.audit_previous_role(group_id, user_id).await;
+        self.execute_with_previous_state(
+            SetUserRoleInGroup {
+                group_id,
+                user_id,
+                role_id: desired_role.id,
+            },
+            previous_role,
+        )
+        .await?;
 
         Ok(())
     }
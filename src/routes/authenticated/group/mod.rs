@@ -1 +1 @@
-mod membership;
+pub(crate) mod membership;
@@ -1,2 +1,7 @@
-mod group;
-mod user;
+pub(crate) mod auth;
+pub(crate) mod cloud;
+pub(crate) mod economy;
+pub(crate) mod group;
+pub(crate) mod oauth;
+pub(crate) mod twostepverification;
+pub(crate) mod user;
@@ -0,0 +1,47 @@
+use crate::client::{AuthMethod, RustbloxClient};
+use crate::endpoint::{Endpoint, UrlBuilder};
+use crate::error::RequestError;
+use crate::structs::oauth::OAuthUserInfo;
+use reqwest::Method;
+
+const BASE_URL: &str = "https://apis.roblox.com/oauth/v1";
+
+/// Gets information about the user that authorized the client's OAuth session.
+/// Used in [`get_oauth_user_info`](RustbloxClient::get_oauth_user_info).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetOAuthUserInfo;
+
+impl Endpoint for GetOAuthUserInfo {
+    type Body = ();
+    type Response = OAuthUserInfo;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL).path("userinfo").build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::OAuth
+    }
+}
+
+impl RustbloxClient {
+    /// **MUST HAVE AN OAUTH SESSION**
+    ///
+    /// Gets information about the user that authorized the client's OAuth session.
+    /// The `openid` scope is needed for this, and the `profile` scope for anything
+    /// past the user's ID.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - You do not have an OAuth session set
+    /// - The access token could not be refreshed
+    /// - The endpoint responds with an error
+    pub async fn get_oauth_user_info(&self) -> Result<OAuthUserInfo, RequestError> {
+        self.execute(GetOAuthUserInfo).await
+    }
+}
@@ -0,0 +1,85 @@
+use crate::client::{AuthMethod, RustbloxClient};
+use crate::endpoint::{Endpoint, UrlBuilder};
+use crate::error::RequestError;
+use crate::structs::twostepverification::TwoStepVerificationToken;
+use reqwest::Method;
+
+const BASE_URL: &str = "https://twostepverification.roblox.com/v1";
+
+/// Submits an authenticator app code for a two-step verification challenge.
+/// Used in [`verify_authenticator_code`](RustbloxClient::verify_authenticator_code).
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyAuthenticatorCode {
+    #[serde(skip)]
+    pub user_id: usize,
+    pub challenge_id: String,
+    pub action_type: String,
+    pub code: String,
+}
+
+impl Endpoint for VerifyAuthenticatorCode {
+    type Body = Self;
+    type Response = TwoStepVerificationToken;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("users")
+            .segment(self.user_id)
+            .path("challenges/authenticator/verify")
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    // Only hands back a token for the challenge being solved
+    fn mutating(&self) -> bool {
+        false
+    }
+}
+
+impl RustbloxClient {
+    /// **MUST AUTHENTICATE**
+    ///
+    /// Submits an authenticator app `code` for the two-step verification challenge
+    /// `challenge_id`, returning a verification token if the code is accepted.
+    ///
+    /// This is done automatically by the
+    /// [`TwoStepVerificationSolver`](crate::twostepverification::TwoStepVerificationSolver).
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - You do not have a `.ROBLOSECURITY` cookie set
+    /// - The endpoint responds with an error
+    ///
+    /// Possible error responses include:
+    /// - Status 400 code 1: The challenge is invalid
+    /// - Status 400 code 10: The code is invalid
+    /// - Status 429 code 9: Too many attempts
+    pub async fn verify_authenticator_code(
+        &self,
+        user_id: usize,
+        challenge_id: String,
+        action_type: String,
+        code: String,
+    ) -> Result<TwoStepVerificationToken, RequestError> {
+        self.execute(VerifyAuthenticatorCode {
+            user_id,
+            challenge_id,
+            action_type,
+            code,
+        })
+        .await
+    }
+}
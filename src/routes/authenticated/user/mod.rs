@@ -1,13 +1,375 @@
-use crate::client::{RequestComponents, RustbloxClient};
+use crate::client::{AuthMethod, RustbloxClient};
+use crate::endpoint::{Endpoint, UrlBuilder};
 use crate::error::RequestError;
 use crate::structs::user::{
-    AuthenticatedUserAgeBracket, AuthenticatedUserCountryCode, AuthenticatedUserRoles,
-    MinimalAuthenticatedUser,
+    AuthenticatedUserAgeBracket, AuthenticatedUserCountryCode, AuthenticatedUserGender,
+    AuthenticatedUserRoles, Birthdate, Gender, MinimalAuthenticatedUser, UserDescription,
 };
-use reqwest::header::{HeaderMap, HeaderValue};
+use crate::validation::validate_display_name;
 use reqwest::Method;
+use serde::Serializer;
+use std::fmt::{Debug, Formatter};
 
 const BASE_URL: &str = "https://users.roblox.com/v1";
+const ACCOUNT_INFO_URL: &str = "https://accountinformation.roblox.com/v1";
+
+/// Changes the authenticated user's display name.
+/// Used in [`change_display_name`](RustbloxClient::change_display_name).
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChangeDisplayName {
+    #[serde(skip)]
+    pub user_id: usize,
+    #[serde(rename = "newDisplayName")]
+    pub new_name: String,
+}
+
+impl Endpoint for ChangeDisplayName {
+    type Body = Self;
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        Method::PATCH
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("users")
+            .segment(self.user_id)
+            .path("display-names")
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn audit_targets(&self) -> Vec<(&'static str, serde_json::Value)> {
+        vec![
+            ("user_id", self.user_id.into()),
+            ("new_display_name", self.new_name.clone().into()),
+        ]
+    }
+}
+
+/// Gets minimal information about the authenticated user.
+/// Used in [`get_authenticated_user`](RustbloxClient::get_authenticated_user).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetAuthenticatedUser;
+
+impl Endpoint for GetAuthenticatedUser {
+    type Body = ();
+    type Response = MinimalAuthenticatedUser;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("users/authenticated")
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+}
+
+/// Gets the authenticated user's age bracket.
+/// Used in [`get_authenticated_user_age_bracket`](RustbloxClient::get_authenticated_user_age_bracket).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetAuthenticatedUserAgeBracket;
+
+impl Endpoint for GetAuthenticatedUserAgeBracket {
+    type Body = ();
+    type Response = AuthenticatedUserAgeBracket;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("users/authenticated/age-bracket")
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+}
+
+/// Gets the authenticated user's country code.
+/// Used in [`get_authenticated_user_country_code`](RustbloxClient::get_authenticated_user_country_code).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetAuthenticatedUserCountryCode;
+
+impl Endpoint for GetAuthenticatedUserCountryCode {
+    type Body = ();
+    type Response = AuthenticatedUserCountryCode;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("users/authenticated/country-code")
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+}
+
+/// Gets the authenticated user's group roles.
+/// Used in [`get_authenticated_user_roles`](RustbloxClient::get_authenticated_user_roles).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetAuthenticatedUserRoles;
+
+impl Endpoint for GetAuthenticatedUserRoles {
+    type Body = ();
+    type Response = AuthenticatedUserRoles;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("users/authenticated/roles")
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+}
+
+/// Validates a display name for the authenticated user.
+/// Used in [`validate_user_display_name`](RustbloxClient::validate_user_display_name).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidateUserDisplayName {
+    pub user_id: usize,
+    pub display_name: String,
+}
+
+impl Endpoint for ValidateUserDisplayName {
+    type Body = ();
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("users")
+            .segment(self.user_id)
+            .path("display-names/validate")
+            .build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        vec![("displayName", self.display_name.clone())]
+    }
+}
+
+/// Gets the authenticated user's profile description.
+/// Used in [`get_user_description`](RustbloxClient::get_user_description).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetUserDescription;
+
+impl Endpoint for GetUserDescription {
+    type Body = ();
+    type Response = UserDescription;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL).path("description").build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+}
+
+/// Changes the authenticated user's profile description.
+/// Used in [`set_authenticated_user_description`](RustbloxClient::set_authenticated_user_description).
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SetAuthenticatedUserDescription {
+    pub description: String,
+}
+
+impl Endpoint for SetAuthenticatedUserDescription {
+    type Body = Self;
+    // None in dry-run mode, since nothing was saved
+    type Response = Option<UserDescription>;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL).path("description").build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn audit_targets(&self) -> Vec<(&'static str, serde_json::Value)> {
+        vec![("description", self.description.clone().into())]
+    }
+}
+
+/// Gets the authenticated user's birthdate.
+/// Used in [`get_authenticated_user_birthdate`](RustbloxClient::get_authenticated_user_birthdate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetAuthenticatedUserBirthdate;
+
+impl Endpoint for GetAuthenticatedUserBirthdate {
+    type Body = ();
+    type Response = Birthdate;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(ACCOUNT_INFO_URL).path("birthdate").build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+}
+
+/// Changes the authenticated user's birthdate. Roblox wants the account's password
+/// for this.
+/// Used in [`set_authenticated_user_birthdate`](RustbloxClient::set_authenticated_user_birthdate).
+#[derive(Serialize, Clone, PartialEq, Eq)]
+pub struct SetAuthenticatedUserBirthdate {
+    #[serde(flatten)]
+    pub birthdate: Birthdate,
+    pub password: String,
+}
+
+// The password stays out of logs
+impl Debug for SetAuthenticatedUserBirthdate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SetAuthenticatedUserBirthdate")
+            .field("birthdate", &self.birthdate)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Endpoint for SetAuthenticatedUserBirthdate {
+    type Body = Self;
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(ACCOUNT_INFO_URL).path("birthdate").build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn audit_targets(&self) -> Vec<(&'static str, serde_json::Value)> {
+        vec![(
+            "birthdate",
+            serde_json::to_value(self.birthdate).unwrap_or_default(),
+        )]
+    }
+}
+
+/// Gets the authenticated user's gender.
+/// Used in [`get_authenticated_user_gender`](RustbloxClient::get_authenticated_user_gender).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetAuthenticatedUserGender;
+
+impl Endpoint for GetAuthenticatedUserGender {
+    type Body = ();
+    type Response = AuthenticatedUserGender;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(ACCOUNT_INFO_URL).path("gender").build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+}
+
+/// Changes the authenticated user's gender.
+/// Used in [`set_authenticated_user_gender`](RustbloxClient::set_authenticated_user_gender).
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SetAuthenticatedUserGender {
+    // Roblox sends genders as numbers, but takes them by name
+    #[serde(serialize_with = "serialize_gender_name")]
+    pub gender: Gender,
+}
+
+fn serialize_gender_name<S>(gender: &Gender, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(gender.as_str())
+}
+
+impl Endpoint for SetAuthenticatedUserGender {
+    type Body = Self;
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(ACCOUNT_INFO_URL).path("gender").build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::Cookie
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn audit_targets(&self) -> Vec<(&'static str, serde_json::Value)> {
+        vec![("gender", self.gender.as_str().into())]
+    }
+}
 
 impl RustbloxClient {
     /// **MUST AUTHENTICATE**
@@ -20,6 +382,8 @@ impl RustbloxClient {
     /// This function will error if:
     /// - You do not have a `.ROBLOSECURITY` cookie set
     /// - The endpoint responds with an error
+    /// - Client-side validation is enabled and `new_name` is not a valid display name.
+    ///   No request is sent in this case.
     ///
     /// Possible error responses include:
     /// - Status 400 code 1: Display name is too short
@@ -35,29 +399,19 @@ impl RustbloxClient {
         user_id: usize,
         new_name: String,
     ) -> Result<(), RequestError> {
-        let url = format!("{BASE_URL}/users/{user_id}/display-names");
-
-        let body = json!({
-            "newDisplayName": new_name,
-        });
-        let data_size = body.clone().to_string().as_bytes().len();
-
-        let mut headers = HeaderMap::new();
-        headers.insert("Content-Length", HeaderValue::from(data_size));
-        headers.insert(
-            "Content-Type",
-            HeaderValue::from_str("application/json").unwrap(),
-        );
-
-        let components = RequestComponents {
-            needs_auth: true,
-            method: Method::PATCH,
-            url,
-            headers: Some(headers),
-            body: Some(body.to_string()),
-        };
+        if self.validate_names {
+            validate_display_name(&new_name).map_err(RequestError::InvalidName)?;
+        }
 
-        self.make_request::<serde_json::Value>(components, false)
+        let previous_name = if self.is_audited() {
+            self.get_user_info(user_id)
+                .await
+                .ok()
+                .map(|user| serde_json::json!({ "display_name": user.display_name }))
+        } else {
+            None
+        };
+        self.execute_with_previous_state(ChangeDisplayName { user_id, new_name }, previous_name)
             .await?;
         Ok(())
     }
@@ -75,20 +429,7 @@ impl RustbloxClient {
     /// Possible error responses include:
     /// - Status 401 code 0: Authorization denied
     pub async fn get_authenticated_user(&self) -> Result<MinimalAuthenticatedUser, RequestError> {
-        let url = format!("{BASE_URL}/users/authenticated");
-
-        let components = RequestComponents {
-            needs_auth: true,
-            method: Method::GET,
-            url,
-            headers: None,
-            body: None,
-        };
-
-        let data = self
-            .make_request::<MinimalAuthenticatedUser>(components, false)
-            .await?;
-        Ok(data)
+        self.execute(GetAuthenticatedUser).await
     }
 
     /// **MUST AUTHENTICATE**
@@ -106,20 +447,7 @@ impl RustbloxClient {
     pub async fn get_authenticated_user_age_bracket(
         &self,
     ) -> Result<AuthenticatedUserAgeBracket, RequestError> {
-        let url = format!("{BASE_URL}/users/authenticated/age-bracket");
-
-        let components = RequestComponents {
-            needs_auth: true,
-            method: Method::GET,
-            url,
-            headers: None,
-            body: None,
-        };
-
-        let data = self
-            .make_request::<AuthenticatedUserAgeBracket>(components, false)
-            .await?;
-        Ok(data)
+        self.execute(GetAuthenticatedUserAgeBracket).await
     }
 
     /// **MUST AUTHENTICATE**
@@ -137,20 +465,7 @@ impl RustbloxClient {
     pub async fn get_authenticated_user_country_code(
         &self,
     ) -> Result<AuthenticatedUserCountryCode, RequestError> {
-        let url = format!("{BASE_URL}/users/authenticated/country-code");
-
-        let components = RequestComponents {
-            needs_auth: true,
-            method: Method::GET,
-            url,
-            headers: None,
-            body: None,
-        };
-
-        let data = self
-            .make_request::<AuthenticatedUserCountryCode>(components, false)
-            .await?;
-        Ok(data)
+        self.execute(GetAuthenticatedUserCountryCode).await
     }
 
     /// **MUST AUTHENTICATE**
@@ -168,20 +483,7 @@ impl RustbloxClient {
     pub async fn get_authenticated_user_roles(
         &self,
     ) -> Result<AuthenticatedUserRoles, RequestError> {
-        let url = format!("{BASE_URL}/users/authenticated/roles");
-
-        let components = RequestComponents {
-            needs_auth: true,
-            method: Method::GET,
-            url,
-            headers: None,
-            body: None,
-        };
-
-        let data = self
-            .make_request::<AuthenticatedUserRoles>(components, false)
-            .await?;
-        Ok(data)
+        self.execute(GetAuthenticatedUserRoles).await
     }
 
     /// **MUST AUTHENTICATE**
@@ -194,6 +496,8 @@ impl RustbloxClient {
     /// This function will error if:
     /// - You do not have a `.ROBLOSECURITY` cookie set
     /// - The endpoint responds with an error
+    /// - Client-side validation is enabled and `display_name` is not a valid display name.
+    ///   No request is sent in this case.
     ///
     /// Possible error responses include:
     /// - Status 400 code 1: Display name is too short
@@ -208,20 +512,174 @@ impl RustbloxClient {
         user_id: usize,
         display_name: String,
     ) -> Result<(), RequestError> {
-        let url =
-            format!("{BASE_URL}/users/{user_id}/display-names/validate?displayName={display_name}");
-
-        let components = RequestComponents {
-            needs_auth: true,
-            method: Method::GET,
-            url,
-            headers: None,
-            body: None,
-        };
+        if self.validate_names {
+            validate_display_name(&display_name).map_err(RequestError::InvalidName)?;
+        }
+
+        self.execute(ValidateUserDisplayName {
+            user_id,
+            display_name,
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// **MUST AUTHENTICATE**
+    ///
+    /// Gets the authenticated user's profile description.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - You do not have a `.ROBLOSECURITY` cookie set
+    /// - The endpoint responds with an error
+    ///
+    /// Possible error responses include:
+    /// - Status 401 code 0: Authorization denied
+    pub async fn get_user_description(&self) -> Result<String, RequestError> {
+        Ok(self.execute(GetUserDescription).await?.description)
+    }
 
-        self.make_request::<serde_json::Value>(components, false)
+    /// **MUST AUTHENTICATE**
+    ///
+    /// Changes the authenticated user's profile description. Returns the description
+    /// as Roblox saved it, which may have been filtered.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - You do not have a `.ROBLOSECURITY` cookie set
+    /// - The endpoint responds with an error
+    ///
+    /// Possible error responses include:
+    /// - Status 400 code 1: The user doesn't exist
+    /// - Status 401 code 0: Authorization denied
+    /// - Status 403 code 0: Token validation failed
+    /// - Status 403 code 3: PIN is locked
+    /// - Status 503 code 2: This feature is currently disabled
+    pub async fn set_authenticated_user_description(
+        &self,
+        description: &str,
+    ) -> Result<String, RequestError> {
+        let previous_description = if self.is_audited() {
+            self.get_user_description()
+                .await
+                .ok()
+                .map(|description| serde_json::json!({ "description": description }))
+        } else {
+            None
+        };
+        let saved = self
+            .execute_with_previous_state(
+                SetAuthenticatedUserDescription {
+                    description: description.to_string(),
+                },
+                previous_description,
+            )
             .await?;
+        Ok(saved.map_or_else(|| description.to_string(), |saved| saved.description))
+    }
+
+    /// **MUST AUTHENTICATE**
+    ///
+    /// Gets the authenticated user's birthdate.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - You do not have a `.ROBLOSECURITY` cookie set
+    /// - The endpoint responds with an error
+    ///
+    /// Possible error responses include:
+    /// - Status 401 code 0: Authorization denied
+    pub async fn get_authenticated_user_birthdate(&self) -> Result<Birthdate, RequestError> {
+        self.execute(GetAuthenticatedUserBirthdate).await
+    }
+
+    /// **MUST AUTHENTICATE**
+    ///
+    /// Changes the authenticated user's birthdate. Roblox asks for the account's
+    /// `password` to confirm the change.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - You do not have a `.ROBLOSECURITY` cookie set
+    /// - The endpoint responds with an error
+    ///
+    /// Possible error responses include:
+    /// - Status 400 code 1: The user doesn't exist
+    /// - Status 400 code 4: The birthdate is invalid
+    /// - Status 401 code 0: Authorization denied
+    /// - Status 403 code 0: Token validation failed
+    /// - Status 403 code 2: PIN is locked
+    /// - Status 403 code 8: The password is incorrect
+    pub async fn set_authenticated_user_birthdate(
+        &self,
+        birthdate: Birthdate,
+        password: &str,
+    ) -> Result<(), RequestError> {
+        let previous_birthdate = if self.is_audited() {
+            self.get_authenticated_user_birthdate()
+                .await
+                .ok()
+                .map(|birthdate| serde_json::json!({ "birthdate": birthdate }))
+        } else {
+            None
+        };
+        self.execute_with_previous_state(
+            SetAuthenticatedUserBirthdate {
+                birthdate,
+                password: password.to_string(),
+            },
+            previous_birthdate,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// **MUST AUTHENTICATE**
+    ///
+    /// Gets the authenticated user's gender.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - You do not have a `.ROBLOSECURITY` cookie set
+    /// - The endpoint responds with an error
+    ///
+    /// Possible error responses include:
+    /// - Status 401 code 0: Authorization denied
+    pub async fn get_authenticated_user_gender(&self) -> Result<Gender, RequestError> {
+        Ok(self.execute(GetAuthenticatedUserGender).await?.gender)
+    }
 
+    /// **MUST AUTHENTICATE**
+    ///
+    /// Changes the authenticated user's gender.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - You do not have a `.ROBLOSECURITY` cookie set
+    /// - The endpoint responds with an error
+    ///
+    /// Possible error responses include:
+    /// - Status 400 code 1: The user doesn't exist
+    /// - Status 400 code 2: The gender is invalid
+    /// - Status 401 code 0: Authorization denied
+    /// - Status 403 code 0: Token validation failed
+    pub async fn set_authenticated_user_gender(&self, gender: Gender) -> Result<(), RequestError> {
+        let previous_gender = if self.is_audited() {
+            self.get_authenticated_user_gender()
+                .await
+                .ok()
+                .map(|gender| serde_json::json!({ "gender": gender.as_str() }))
+        } else {
+            None
+        };
+        self.execute_with_previous_state(SetAuthenticatedUserGender { gender }, previous_gender)
+            .await?;
         Ok(())
     }
 }
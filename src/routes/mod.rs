@@ -1,2 +1,2 @@
-mod authenticated;
-mod unauthenticated;
+pub(crate) mod authenticated;
+pub(crate) mod unauthenticated;
@@ -1,28 +1,43 @@
 use reqwest::Method;
 
-use crate::{client::{RequestComponents, RustbloxClient}, error::RequestError, structs::group::GroupInfo};
+use crate::{
+    client::RustbloxClient,
+    endpoint::{Endpoint, UrlBuilder},
+    error::RequestError,
+    structs::group::GroupInfo,
+};
 
 const BASE_URL: &str = "https://groups.roblox.com";
 
+/// Gets information about a group.
+/// Used in [`get_group_info`](RustbloxClient::get_group_info).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetGroupInfo {
+    pub group_id: usize,
+}
+
+impl Endpoint for GetGroupInfo {
+    type Body = ();
+    type Response = GroupInfo;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("v1/groups")
+            .segment(self.group_id)
+            .build()
+    }
+}
+
 impl RustbloxClient {
     /// Gets information about a given `group_id`.
     ///
     /// This function will error if:
     /// - The endpoint reqponds with an error.
     pub async fn get_group_info(&self, group_id: usize) -> Result<GroupInfo, RequestError> {
-        let url = format!("{BASE_URL}/v1/groups/{group_id}");
-
-        let components = RequestComponents {
-            needs_auth: false,
-            method: Method::GET,
-            url,
-            headers: None,
-            body: None,
-        };
-        let info = self
-            .make_request::<GroupInfo>(components, false)
-            .await?;
-
-        Ok(info)
+        self.execute(GetGroupInfo { group_id }).await
     }
 }
@@ -1,12 +1,125 @@
-use crate::client::{RequestComponents, RustbloxClient};
+use crate::client::RustbloxClient;
+use crate::endpoint::{page_query, Endpoint, UrlBuilder};
 use crate::error::RequestError;
-use crate::structs::group::{GroupMemberInfo, GroupRolesList, UserGroup};
-use crate::structs::user::MinimalUserInfo;
+use crate::structs::group::{GroupMemberInfo, GroupMemberUser, GroupRolesList, UserGroup};
 use crate::structs::{DataWrapper, Page, SortOrder};
 use reqwest::Method;
 
 const BASE_URL: &str = "https://groups.roblox.com";
 
+/// Gets a page of a group's members.
+/// Used in [`get_group_members`](RustbloxClient::get_group_members).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetGroupMembers {
+    pub group_id: usize,
+    pub limit: Option<usize>,
+    pub cursor: Option<String>,
+    pub sort_order: Option<SortOrder>,
+}
+
+impl Endpoint for GetGroupMembers {
+    type Body = ();
+    type Response = Page<GroupMemberInfo>;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("v1/groups")
+            .segment(self.group_id)
+            .path("users")
+            .build()
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        page_query(self.limit, self.cursor.as_ref(), self.sort_order)
+    }
+}
+
+/// Gets a group's roles.
+/// Used in [`get_group_roles`](RustbloxClient::get_group_roles).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetGroupRoles {
+    pub group_id: usize,
+}
+
+impl Endpoint for GetGroupRoles {
+    type Body = ();
+    type Response = GroupRolesList;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("v1/groups")
+            .segment(self.group_id)
+            .path("roles")
+            .build()
+    }
+}
+
+/// Gets a page of the members in a certain role of a group.
+/// Used in [`get_group_role_members`](RustbloxClient::get_group_role_members).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetGroupRoleMembers {
+    pub group_id: usize,
+    pub role_id: usize,
+    pub limit: Option<usize>,
+    pub cursor: Option<String>,
+    pub sort_order: Option<SortOrder>,
+}
+
+impl Endpoint for GetGroupRoleMembers {
+    type Body = ();
+    type Response = Page<GroupMemberUser>;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("v1/groups")
+            .segment(self.group_id)
+            .path("roles")
+            .segment(self.role_id)
+            .path("users")
+            .build()
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        page_query(self.limit, self.cursor.as_ref(), self.sort_order)
+    }
+}
+
+/// Gets the groups a user is in, along with their role in each.
+/// Used in [`get_user_group_roles`](RustbloxClient::get_user_group_roles).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetUserGroupRoles {
+    pub user_id: usize,
+}
+
+impl Endpoint for GetUserGroupRoles {
+    type Body = ();
+    type Response = DataWrapper<UserGroup>;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("v1/users")
+            .segment(self.user_id)
+            .path("groups/roles")
+            .build()
+    }
+}
+
 impl RustbloxClient {
     /// Gets a list of the group's members.
     ///
@@ -24,30 +137,13 @@ impl RustbloxClient {
         cursor: Option<String>,
         sort_order: Option<SortOrder>,
     ) -> Result<Page<GroupMemberInfo>, RequestError> {
-        let real_limit = if limit.is_some() { limit.unwrap() } else { 10 };
-        let mut url = format!("{BASE_URL}/v1/groups/{group_id}/users?limit={real_limit}");
-        if cursor.is_some() {
-            url = format!("{url}&cursor={}", cursor.unwrap());
-        }
-        if sort_order.is_some() {
-            match sort_order.unwrap() {
-                SortOrder::Ascending => url = format!("{url}&sortOrder=Asc"),
-                SortOrder::Descending => url = format!("{url}&sortOrder=Desc"),
-            }
-        }
-
-        let components = RequestComponents {
-            needs_auth: false,
-            method: Method::GET,
-            url,
-            headers: None,
-            body: None,
-        };
-        let users = self
-            .make_request::<Page<GroupMemberInfo>>(components, false)
-            .await?;
-
-        Ok(users)
+        self.execute(GetGroupMembers {
+            group_id,
+            limit,
+            cursor,
+            sort_order,
+        })
+        .await
     }
 
     /// Gets a list of the group's roles.
@@ -57,19 +153,7 @@ impl RustbloxClient {
     /// This function will error if:
     /// - The endpoint responds with an error.
     pub async fn get_group_roles(&self, group_id: usize) -> Result<GroupRolesList, RequestError> {
-        let url = format!("{BASE_URL}/v1/groups/{group_id}/roles");
-
-        let components = RequestComponents {
-            needs_auth: false,
-            method: Method::GET,
-            url,
-            headers: None,
-            body: None,
-        };
-        let ranks = self
-            .make_request::<GroupRolesList>(components, false)
-            .await?;
-        Ok(ranks)
+        self.execute(GetGroupRoles { group_id }).await
     }
 
     /// Gets the members in a certain role of a certain group.
@@ -93,32 +177,15 @@ impl RustbloxClient {
         limit: Option<usize>,
         cursor: Option<String>,
         sort_order: Option<SortOrder>,
-    ) -> Result<Page<MinimalUserInfo>, RequestError> {
-        let real_limit = if limit.is_some() { limit.unwrap() } else { 10 };
-        let mut url =
-            format!("{BASE_URL}/v1/groups/{group_id}/roles/{role_id}/users?limit={real_limit}");
-        if cursor.is_some() {
-            url = format!("{url}&cursor={}", cursor.unwrap());
-        }
-        if sort_order.is_some() {
-            match sort_order.unwrap() {
-                SortOrder::Ascending => url = format!("{url}&sortOrder=Asc"),
-                SortOrder::Descending => url = format!("{url}&sortOrder=Desc"),
-            }
-        }
-
-        let components = RequestComponents {
-            needs_auth: false,
-            method: Method::GET,
-            url: url.clone(),
-            headers: None,
-            body: None,
-        };
-
-        let role_members_data = self
-            .make_request::<Page<MinimalUserInfo>>(components, false)
-            .await?;
-        Ok(role_members_data)
+    ) -> Result<Page<GroupMemberUser>, RequestError> {
+        self.execute(GetGroupRoleMembers {
+            group_id,
+            role_id,
+            limit,
+            cursor,
+            sort_order,
+        })
+        .await
     }
 
     /// This doesn't need authentication and will be moved at a later date.
@@ -131,19 +198,6 @@ impl RustbloxClient {
         &self,
         user_id: usize,
     ) -> Result<DataWrapper<UserGroup>, RequestError> {
-        let url = format!("{BASE_URL}/v1/users/{user_id}/groups/roles");
-
-        let components = RequestComponents {
-            needs_auth: false,
-            method: Method::GET,
-            url,
-            headers: None,
-            body: None,
-        };
-        let info = self
-            .make_request::<DataWrapper<UserGroup>>(components, false)
-            .await?;
-
-        Ok(info)
+        self.execute(GetUserGroupRoles { user_id }).await
     }
 }
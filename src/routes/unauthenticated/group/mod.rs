@@ -1,2 +1,2 @@
-mod membership;
-mod groups;
+pub(crate) mod membership;
+pub(crate) mod groups;
@@ -1,2 +1,4 @@
-mod group;
-mod user;
+pub(crate) mod group;
+pub(crate) mod oauth;
+pub(crate) mod presence;
+pub(crate) mod user;
@@ -0,0 +1,294 @@
+use crate::client::RustbloxClient;
+use crate::endpoint::{BodyFormat, Endpoint, UrlBuilder};
+use crate::error::RequestError;
+use crate::oauth::{OAuthApp, PkceVerifier};
+use crate::structs::oauth::{OAuthToken, OAuthTokenIntrospection};
+use reqwest::Method;
+use std::fmt::{Debug, Formatter};
+
+const BASE_URL: &str = "https://apis.roblox.com/oauth/v1";
+
+/// Exchanges an authorization code for a set of tokens.
+/// Used in [`exchange_oauth_code`](RustbloxClient::exchange_oauth_code).
+#[derive(Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "grant_type", rename = "authorization_code")]
+pub struct ExchangeOAuthCode {
+    pub client_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    pub code: String,
+    pub code_verifier: String,
+}
+
+impl Debug for ExchangeOAuthCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Don't leak the secret or code into logs
+        f.debug_struct("ExchangeOAuthCode")
+            .field("client_id", &self.client_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Endpoint for ExchangeOAuthCode {
+    type Body = Self;
+    type Response = OAuthToken;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL).path("token").build()
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn body_format(&self) -> BodyFormat {
+        BodyFormat::Form
+    }
+
+    // Needed to sign in, even in dry-run mode
+    fn mutating(&self) -> bool {
+        false
+    }
+}
+
+/// Gets information about an access or refresh token.
+/// Used in [`introspect_oauth_token`](RustbloxClient::introspect_oauth_token).
+#[derive(Serialize, Clone, PartialEq, Eq)]
+pub struct IntrospectOAuthToken {
+    pub client_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    pub token: String,
+}
+
+impl Debug for IntrospectOAuthToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Don't leak the secret or token into logs
+        f.debug_struct("IntrospectOAuthToken")
+            .field("client_id", &self.client_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Endpoint for IntrospectOAuthToken {
+    type Body = Self;
+    type Response = OAuthTokenIntrospection;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL).path("token/introspect").build()
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn body_format(&self) -> BodyFormat {
+        BodyFormat::Form
+    }
+
+    // Only reads the token's details
+    fn mutating(&self) -> bool {
+        false
+    }
+}
+
+/// Trades a refresh token for a new set of tokens.
+/// Used in [`refresh_oauth_token`](RustbloxClient::refresh_oauth_token).
+#[derive(Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "grant_type", rename = "refresh_token")]
+pub struct RefreshOAuthToken {
+    pub client_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    pub refresh_token: String,
+}
+
+impl Debug for RefreshOAuthToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Don't leak the secret or token into logs
+        f.debug_struct("RefreshOAuthToken")
+            .field("client_id", &self.client_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Endpoint for RefreshOAuthToken {
+    type Body = Self;
+    type Response = OAuthToken;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL).path("token").build()
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn body_format(&self) -> BodyFormat {
+        BodyFormat::Form
+    }
+
+    // Needed to stay signed in, even in dry-run mode
+    fn mutating(&self) -> bool {
+        false
+    }
+}
+
+/// Revokes a refresh token, along with the access tokens issued from it.
+/// Used in [`revoke_oauth_token`](RustbloxClient::revoke_oauth_token).
+#[derive(Serialize, Clone, PartialEq, Eq)]
+pub struct RevokeOAuthToken {
+    pub client_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    pub token: String,
+}
+
+impl Debug for RevokeOAuthToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Don't leak the secret or token into logs
+        f.debug_struct("RevokeOAuthToken")
+            .field("client_id", &self.client_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Endpoint for RevokeOAuthToken {
+    type Body = Self;
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL).path("token/revoke").build()
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn body_format(&self) -> BodyFormat {
+        BodyFormat::Form
+    }
+
+    fn audit_targets(&self) -> Vec<(&'static str, serde_json::Value)> {
+        vec![("client_id", self.client_id.clone().into())]
+    }
+}
+
+impl RustbloxClient {
+    /// Exchanges the authorization `code` that Roblox sent to `app`'s redirect URI for a
+    /// set of tokens. `verifier` must be the one returned alongside the authorization URL
+    /// by [`authorization_url`](OAuthApp::authorization_url).
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - The endpoint responds with an error
+    ///
+    /// Possible error responses include:
+    /// - Status 400: The code is invalid, expired, or was already used
+    /// - Status 400: The verifier doesn't match the code challenge
+    /// - Status 401: The client ID or secret is invalid
+    pub async fn exchange_oauth_code(
+        &self,
+        app: &OAuthApp,
+        code: String,
+        verifier: &PkceVerifier,
+    ) -> Result<OAuthToken, RequestError> {
+        self.execute(ExchangeOAuthCode {
+            client_id: app.client_id.clone(),
+            client_secret: app.client_secret.clone(),
+            code,
+            code_verifier: verifier.as_str().to_string(),
+        })
+        .await
+    }
+
+    /// Gets information about an access or refresh `token` issued to `app`, such as
+    /// whether it is still active and which scopes it was granted.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - The endpoint responds with an error
+    ///
+    /// Possible error responses include:
+    /// - Status 401: The client ID or secret is invalid
+    pub async fn introspect_oauth_token(
+        &self,
+        app: &OAuthApp,
+        token: String,
+    ) -> Result<OAuthTokenIntrospection, RequestError> {
+        self.execute(IntrospectOAuthToken {
+            client_id: app.client_id.clone(),
+            client_secret: app.client_secret.clone(),
+            token,
+        })
+        .await
+    }
+
+    /// Trades a `refresh_token` issued to `app` for a new set of tokens.
+    ///
+    /// Refresh tokens are rotated: the returned token contains a new refresh token,
+    /// and the old one stops working. Make sure to store the new one.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - The endpoint responds with an error
+    ///
+    /// Possible error responses include:
+    /// - Status 400: The refresh token is invalid, expired, or was revoked
+    /// - Status 401: The client ID or secret is invalid
+    pub async fn refresh_oauth_token(
+        &self,
+        app: &OAuthApp,
+        refresh_token: String,
+    ) -> Result<OAuthToken, RequestError> {
+        self.execute(RefreshOAuthToken {
+            client_id: app.client_id.clone(),
+            client_secret: app.client_secret.clone(),
+            refresh_token,
+        })
+        .await
+    }
+
+    /// Revokes a refresh `token` issued to `app`, along with every access token issued
+    /// from it. Use this when a user disconnects their account from your app.
+    ///
+    /// # Errors
+    ///
+    /// This function will error if:
+    /// - The endpoint responds with an error
+    ///
+    /// Possible error responses include:
+    /// - Status 401: The client ID or secret is invalid
+    pub async fn revoke_oauth_token(
+        &self,
+        app: &OAuthApp,
+        token: String,
+    ) -> Result<(), RequestError> {
+        self.execute(RevokeOAuthToken {
+            client_id: app.client_id.clone(),
+            client_secret: app.client_secret.clone(),
+            token,
+        })
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use crate::client::{AuthMethod, RustbloxClient};
+use crate::endpoint::{Endpoint, UrlBuilder};
+use crate::error::RequestError;
+use crate::structs::presence::{LastOnline, LastOnlineTimestamps, UserPresence, UserPresences};
+use reqwest::Method;
+
+const BASE_URL: &str = "https://presence.roblox.com/v1";
+
+/// How many users Roblox will look up in one presence request.
+pub const PRESENCE_BATCH_SIZE: usize = 50;
+
+/// Gets the presences of a batch of users. Roblox only shows what users are
+/// playing to accounts that are allowed to see it, so set `authenticated` to
+/// send the `.ROBLOSECURITY` cookie along.
+/// Used in [`get_user_presences`](RustbloxClient::get_user_presences).
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GetUserPresences {
+    pub user_ids: Vec<usize>,
+    #[serde(skip)]
+    pub authenticated: bool,
+}
+
+impl Endpoint for GetUserPresences {
+    type Body = Self;
+    type Response = UserPresences;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL).path("presence/users").build()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        if self.authenticated {
+            AuthMethod::Cookie
+        } else {
+            AuthMethod::None
+        }
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    // A lookup, even though it's a POST
+    fn mutating(&self) -> bool {
+        false
+    }
+}
+
+/// Gets when a batch of users were last online.
+/// Used in [`get_users_last_online`](RustbloxClient::get_users_last_online).
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLastOnline {
+    pub user_ids: Vec<usize>,
+}
+
+impl Endpoint for GetLastOnline {
+    type Body = Self;
+    type Response = LastOnlineTimestamps;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("presence/last-online")
+            .build()
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    // A lookup, even though it's a POST
+    fn mutating(&self) -> bool {
+        false
+    }
+}
+
+impl RustbloxClient {
+    /// Gets the presences of the given users. Any number of users can be passed in;
+    /// they're looked up [`PRESENCE_BATCH_SIZE`] at a time.
+    ///
+    /// If the client has a `.ROBLOSECURITY` cookie, it's sent along, so the place,
+    /// universe and job IDs are filled in for users who let that account see them.
+    /// If Roblox turns the cookie down (and it can't be refreshed), the rest of the
+    /// lookup is done without it instead of failing, so the presences are still
+    /// there, just without the details.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if any of the requests could not be made, or if the endpoint responded with an error.
+    ///
+    /// Possible error responses:
+    /// - Status 400 code 1: Too many users were requested
+    /// - Status 429 code 0: Too many requests
+    pub async fn get_user_presences(
+        &self,
+        user_ids: &[usize],
+    ) -> Result<Vec<UserPresence>, RequestError> {
+        let mut authenticated = self.roblox_cookie().is_some();
+        let mut presences = Vec::with_capacity(user_ids.len());
+        for batch in user_ids.chunks(PRESENCE_BATCH_SIZE) {
+            let result = self
+                .execute(GetUserPresences {
+                    user_ids: batch.to_vec(),
+                    authenticated,
+                })
+                .await;
+            let response = match result {
+                // Presences don't need the cookie, so an expired one shouldn't fail the lookup
+                Err(RequestError::ExpiredCookie(error)) if authenticated => {
+                    warn!("The .ROBLOSECURITY cookie was rejected, so presences are looked up without it:\n{error}");
+                    authenticated = false;
+                    self.execute(GetUserPresences {
+                        user_ids: batch.to_vec(),
+                        authenticated,
+                    })
+                    .await?
+                }
+                result => result?,
+            };
+            presences.extend(response.user_presences);
+        }
+
+        Ok(presences)
+    }
+
+    /// Gets when the given users were last online. Any number of users can be passed in;
+    /// they're looked up [`PRESENCE_BATCH_SIZE`] at a time.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if any of the requests could not be made, or if the endpoint responded with an error.
+    ///
+    /// Possible error responses:
+    /// - Status 400 code 1: Too many users were requested
+    /// - Status 429 code 0: Too many requests
+    pub async fn get_users_last_online(
+        &self,
+        user_ids: &[usize],
+    ) -> Result<Vec<LastOnline>, RequestError> {
+        let mut timestamps = Vec::with_capacity(user_ids.len());
+        for batch in user_ids.chunks(PRESENCE_BATCH_SIZE) {
+            let response = self
+                .execute(GetLastOnline {
+                    user_ids: batch.to_vec(),
+                })
+                .await?;
+            timestamps.extend(response.last_online_timestamps);
+        }
+
+        Ok(timestamps)
+    }
+}
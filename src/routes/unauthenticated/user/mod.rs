@@ -1,14 +1,162 @@
-use crate::client::RequestComponents;
 use crate::client::RustbloxClient;
+use crate::endpoint::{page_query, Endpoint, UrlBuilder};
 use crate::error::RequestError;
-use crate::structs::user::{MinimalUserInfo, PreviousUsername, UserInfo};
+use crate::structs::user::{
+    MinimalUserInfo, PreviousUsername, UserIdLookupResult, UserInfo, UserSearchResult,
+    UsernameLookupResult,
+};
 use crate::structs::DataWrapper;
 use crate::structs::{Page, SortOrder};
-use reqwest::header::{HeaderMap, HeaderValue};
 use reqwest::Method;
 
 const BASE_URL: &str = "https://users.roblox.com/v1";
 
+/// Gets a page of a user's previous usernames.
+/// Used in [`get_previous_usernames`](RustbloxClient::get_previous_usernames).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetPreviousUsernames {
+    pub user_id: usize,
+    pub limit: Option<usize>,
+    pub cursor: Option<String>,
+    pub sort_order: Option<SortOrder>,
+}
+
+impl Endpoint for GetPreviousUsernames {
+    type Body = ();
+    type Response = Page<PreviousUsername>;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("users")
+            .segment(self.user_id)
+            .path("username-history")
+            .build()
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        page_query(self.limit, self.cursor.as_ref(), self.sort_order)
+    }
+}
+
+/// Gets the info about a user.
+/// Used in [`get_user_info`](RustbloxClient::get_user_info).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetUserInfo {
+    pub user_id: usize,
+}
+
+impl Endpoint for GetUserInfo {
+    type Body = ();
+    type Response = UserInfo;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL)
+            .path("users")
+            .segment(self.user_id)
+            .build()
+    }
+}
+
+/// Looks up users by their IDs.
+/// Used in [`get_users_from_ids`](RustbloxClient::get_users_from_ids).
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GetUsersFromIds {
+    pub user_ids: Vec<usize>,
+    #[serde(rename = "excludeBannedUsers")]
+    pub exclude_banned: bool,
+}
+
+impl Endpoint for GetUsersFromIds {
+    type Body = Self;
+    type Response = DataWrapper<UserIdLookupResult>;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL).path("users").build()
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    // A lookup, even though it's a POST
+    fn mutating(&self) -> bool {
+        false
+    }
+}
+
+/// Looks up users by their usernames.
+/// Used in [`get_users_from_usernames`](RustbloxClient::get_users_from_usernames).
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GetUsersFromUsernames {
+    pub usernames: Vec<String>,
+    #[serde(rename = "excludeBannedUsers")]
+    pub exclude_banned: bool,
+}
+
+impl Endpoint for GetUsersFromUsernames {
+    type Body = Self;
+    type Response = DataWrapper<UsernameLookupResult>;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL).path("usernames/users").build()
+    }
+
+    fn body(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    // A lookup, even though it's a POST
+    fn mutating(&self) -> bool {
+        false
+    }
+}
+
+/// Searches for users by keyword.
+/// Used in [`search_user`](RustbloxClient::search_user).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchUser {
+    pub keyword: String,
+    pub limit: Option<usize>,
+    pub cursor: Option<String>,
+}
+
+impl Endpoint for SearchUser {
+    type Body = ();
+    type Response = Page<UserSearchResult>;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        UrlBuilder::new(BASE_URL).path("users/search").build()
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![("keyword", self.keyword.clone())];
+        query.extend(page_query(self.limit, self.cursor.as_ref(), None));
+        query
+    }
+}
+
 impl RustbloxClient {
     /// Gets a user's previous usernames, given their user ID.
     ///
@@ -25,30 +173,87 @@ impl RustbloxClient {
         cursor: Option<String>,
         sort_order: Option<SortOrder>,
     ) -> Result<Page<PreviousUsername>, RequestError> {
-        let real_limit = if limit.is_some() { limit.unwrap() } else { 10 };
-        let mut url = format!("{BASE_URL}/users/{id}/username-history?limit={real_limit}");
-        if cursor.is_some() {
-            url = format!("{url}&cursor={}", cursor.unwrap());
-        }
-        if sort_order.is_some() {
-            match sort_order.unwrap() {
-                SortOrder::Ascending => url = format!("{url}&sortOrder=Asc"),
-                SortOrder::Descending => url = format!("{url}&sortOrder=Desc"),
+        self.execute(GetPreviousUsernames {
+            user_id: id,
+            limit,
+            cursor,
+            sort_order,
+        })
+        .await
+    }
+
+    /// Gets every username a user has had before their current one, oldest first.
+    /// This goes through every page of [`get_previous_usernames`](RustbloxClient::get_previous_usernames).
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if any of the requests could not be made, or if the endpoint responded with an error.
+    ///
+    /// Possible error responses:
+    /// - Status 400 code 3: The user ID is invalid
+    pub async fn get_username_history(&self, id: usize) -> Result<Vec<String>, RequestError> {
+        let mut history = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = self
+                .get_previous_usernames(id, Some(100), cursor, Some(SortOrder::Ascending))
+                .await?;
+            history.extend(page.data.into_iter().map(|previous| previous.name));
+
+            cursor = page.next_page_cursor;
+            if cursor.is_none() {
+                return Ok(history);
             }
         }
+    }
+
+    /// Finds users who used to go by `username`, but don't anymore (i.e. to spot someone
+    /// taking over a name that belonged to somebody else).
+    ///
+    /// Roblox has no direct way to look this up, so this combines two sources: the user
+    /// [`get_users_from_usernames`](RustbloxClient::get_users_from_usernames) resolves the
+    /// name to (if they've since changed it), and the first 100 results of
+    /// [`search_user`](RustbloxClient::search_user) that list it among their previous usernames.
+    /// Both are case-insensitive. Someone who held the name long ago and isn't found by
+    /// either won't be returned.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if any of the requests could not be made, or if the endpoint responded with an error.
+    ///
+    /// Possible error responses:
+    /// - Status 400 code 5: The username (keyword) was filtered
+    /// - Status 400 code 6: The username (keyword) is too short
+    /// - Status 429 code 4: Too many requests
+    pub async fn find_previous_holders(
+        &self,
+        username: &str,
+    ) -> Result<Vec<MinimalUserInfo>, RequestError> {
+        let mut holders: Vec<MinimalUserInfo> = Vec::new();
 
-        let components = RequestComponents {
-            needs_auth: false,
-            method: Method::GET,
-            url: url.clone(),
-            headers: None,
-            body: None,
-        };
+        // Lookups by username also match previous usernames, so a result with a
+        // different current name used to have this one
+        for user in self.get_users_from_usernames(vec![username], false).await? {
+            if !user.name.eq_ignore_ascii_case(username) {
+                holders.push(user.into());
+            }
+        }
 
-        let previous_usernames_data = self
-            .make_request::<Page<PreviousUsername>>(components, false)
+        let results = self
+            .search_user(username.to_string(), Some(100), None)
             .await?;
-        Ok(previous_usernames_data)
+        for user in results.data {
+            let held_it = !user.name.eq_ignore_ascii_case(username)
+                && user
+                    .previous_usernames
+                    .iter()
+                    .any(|previous| previous.eq_ignore_ascii_case(username));
+            if held_it && !holders.iter().any(|holder| holder.id == user.id) {
+                holders.push(user.into());
+            }
+        }
+
+        Ok(holders)
     }
 
     /// Gets the info about a user from their user ID.
@@ -60,18 +265,7 @@ impl RustbloxClient {
     /// Possible error responses:
     /// - Status 404 code 3: The user ID is invalid
     pub async fn get_user_info(&self, id: usize) -> Result<UserInfo, RequestError> {
-        let url = format!("{BASE_URL}/users/{id}");
-        let components = RequestComponents {
-            needs_auth: false,
-            method: Method::GET,
-            url: url.clone(),
-            headers: None,
-            body: None,
-        };
-
-        let user_info = self.make_request::<UserInfo>(components, false).await?;
-
-        Ok(user_info)
+        self.execute(GetUserInfo { user_id: id }).await
     }
 
     /// Searches for users by their IDs.
@@ -86,31 +280,12 @@ impl RustbloxClient {
         &self,
         ids: Vec<usize>,
         exclude_banned: bool,
-    ) -> Result<Vec<MinimalUserInfo>, RequestError> {
-        let url = format!("{BASE_URL}/users");
-        let data_json = json!({
-            "userIds": ids,
-            "excludeBannedUsers": exclude_banned
-        });
-        let data_size = data_json.clone().to_string().as_bytes().len();
-
-        let mut headers = HeaderMap::new();
-        headers.insert("Content-Length", HeaderValue::from(data_size));
-        headers.insert(
-            "Content-Type",
-            HeaderValue::from_str("application/json").unwrap(),
-        );
-
-        let components = RequestComponents {
-            needs_auth: false,
-            method: Method::POST,
-            url: url.clone(),
-            headers: Some(headers),
-            body: Some(data_json.to_string()),
-        };
-
+    ) -> Result<Vec<UserIdLookupResult>, RequestError> {
         let response = self
-            .make_request::<DataWrapper<MinimalUserInfo>>(components, false)
+            .execute(GetUsersFromIds {
+                user_ids: ids,
+                exclude_banned,
+            })
             .await?;
 
         Ok(response.data)
@@ -128,31 +303,12 @@ impl RustbloxClient {
         &self,
         usernames: Vec<&str>,
         exclude_banned: bool,
-    ) -> Result<Vec<MinimalUserInfo>, RequestError> {
-        let url = format!("{BASE_URL}/usernames/users");
-        let data_json = json!({
-            "usernames": usernames,
-            "excludeBannedUsers": exclude_banned
-        });
-        let data_size = data_json.clone().to_string().as_bytes().len();
-
-        let mut headers = HeaderMap::new();
-        headers.insert("Content-Length", HeaderValue::from(data_size));
-        headers.insert(
-            "Content-Type",
-            HeaderValue::from_str("application/json").unwrap(),
-        );
-
-        let components = RequestComponents {
-            needs_auth: false,
-            method: Method::POST,
-            url: url.clone(),
-            headers: Some(headers),
-            body: Some(data_json.to_string()),
-        };
-
+    ) -> Result<Vec<UsernameLookupResult>, RequestError> {
         let response = self
-            .make_request::<DataWrapper<MinimalUserInfo>>(components, false)
+            .execute(GetUsersFromUsernames {
+                usernames: usernames.into_iter().map(String::from).collect(),
+                exclude_banned,
+            })
             .await?;
 
         Ok(response.data)
@@ -173,25 +329,12 @@ impl RustbloxClient {
         username: String,
         limit: Option<usize>,
         page_cursor: Option<String>,
-    ) -> Result<Page<MinimalUserInfo>, RequestError> {
-        let real_limit = if limit.is_some() { limit.unwrap() } else { 10 };
-        let mut url = format!("{BASE_URL}/users/search?keyword={username}&limit={real_limit}");
-        if page_cursor.is_some() {
-            url = format!("{}&cursor={}", url, page_cursor.unwrap());
-        }
-
-        let components = RequestComponents {
-            needs_auth: false,
-            method: Method::GET,
-            url: url.clone(),
-            headers: None,
-            body: None,
-        };
-
-        let response = self
-            .make_request::<Page<MinimalUserInfo>>(components, false)
-            .await?;
-
-        Ok(response)
+    ) -> Result<Page<UserSearchResult>, RequestError> {
+        self.execute(SearchUser {
+            keyword: username,
+            limit,
+            cursor: page_cursor,
+        })
+        .await
     }
 }
@@ -0,0 +1,77 @@
+/// How many Robux Roblox charges to change a username.
+pub const USERNAME_CHANGE_COST: u64 = 1000;
+
+/// Why a username is being validated. Roblox applies slightly different rules to each.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum UsernameContext {
+    Signup,
+    UsernameChange,
+}
+
+/// Roblox's verdict on a username.
+/// Used in [`validate_username_for_change`](crate::client::RustbloxClient::validate_username_for_change).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UsernameValidation {
+    pub code: i16,
+    pub message: String,
+}
+
+impl UsernameValidation {
+    /// Sorts the response code into a [`UsernameStatus`].
+    #[must_use]
+    pub fn status(&self) -> UsernameStatus {
+        match self.code {
+            0 => UsernameStatus::Valid,
+            1 => UsernameStatus::Taken,
+            // 10 means it might contain private information, which is moderation too
+            2 | 10 => UsernameStatus::Moderated,
+            _ => UsernameStatus::Invalid(self.message.clone()),
+        }
+    }
+}
+
+/// Whether a username can be used, and if not, why.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum UsernameStatus {
+    /// The username can be used.
+    Valid,
+    /// Someone else already has the username.
+    Taken,
+    /// The username isn't appropriate for Roblox.
+    Moderated,
+    /// The username breaks one of Roblox's rules. Contains Roblox's explanation.
+    Invalid(String),
+}
+
+/// Contains the existing usernames that match a search.
+/// Used in [`is_username_available`](crate::client::RustbloxClient::is_username_available).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExistingUsernames {
+    pub usernames: Vec<String>,
+}
+
+/// Everything to know before changing the authenticated user's username.
+/// Returned by [`prepare_username_change`](crate::client::RustbloxClient::prepare_username_change).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UsernameChangeQuote {
+    /// The authenticated user's username right now.
+    pub current_username: String,
+    /// The username that would be changed to.
+    pub username: String,
+    /// Whether Roblox would accept the username.
+    pub status: UsernameStatus,
+    /// How many Robux the change costs.
+    pub cost: u64,
+    /// How many Robux the account has.
+    pub balance: u64,
+}
+
+impl UsernameChangeQuote {
+    /// Returns whether the change would go through: the username is valid and the
+    /// account can afford it.
+    #[must_use]
+    pub fn can_change(&self) -> bool {
+        self.status == UsernameStatus::Valid && self.balance >= self.cost
+    }
+}
@@ -0,0 +1,33 @@
+/// Contains information about an Open Cloud API key.
+/// Used in [`introspect_api_key`](crate::client::RustbloxClient::introspect_api_key).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyInfo {
+    pub name: String,
+    pub authorized_user_id: Option<usize>,
+    #[serde(default)]
+    pub scopes: Vec<ApiKeyScope>,
+    pub enabled: bool,
+    pub expired: bool,
+    pub expiration_time_utc: Option<String>,
+}
+
+impl ApiKeyInfo {
+    /// Returns whether or not the key is allowed to perform `operation` in `scope`
+    /// (i.e. `"write"` in `"universe-messaging-service:publish"`).
+    #[must_use]
+    pub fn allows(&self, scope: &str, operation: &str) -> bool {
+        self.scopes
+            .iter()
+            .any(|s| s.name == scope && s.operations.iter().any(|o| o == operation))
+    }
+}
+
+/// Represents a scope that an Open Cloud API key has been granted, along with
+/// the operations it can perform in it. Used as a component of [`ApiKeyInfo`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ApiKeyScope {
+    pub name: String,
+    #[serde(default)]
+    pub operations: Vec<String>,
+}
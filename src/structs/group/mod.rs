@@ -1,17 +1,17 @@
-use crate::structs::user::MinimalUserInfo;
+use crate::structs::user::{MinimalUserInfo, UserRef};
 
 /// Represents a join request to a group. Used in
 /// [`get_user_join_request`](crate::client::RustbloxClient::get_user_join_request)
 /// as well as in [`batch_get_requests`](crate::client::RustbloxClient::batch_get_requests).
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct JoinRequest {
-    pub requester: MinimalUserInfo,
+    pub requester: GroupMemberUser,
     pub created: String,
 }
 
 /// Represents a role in a group. Used as a component
 /// of [`GroupRolesList`].
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct GroupRole {
     pub id: usize,
@@ -23,7 +23,7 @@ pub struct GroupRole {
 
 /// Represents a list of roles in a group. Used in
 /// [`get_group_roles`](crate::client::RustbloxClient::get_group_roles).
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct GroupRolesList {
     pub group_id: usize,
@@ -32,7 +32,7 @@ pub struct GroupRolesList {
 
 /// Represents a group that a user is in, containing the group info
 /// and info about the user's rank/role in it.
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct UserGroup {
     pub group: GroupInfo,
@@ -42,22 +42,23 @@ pub struct UserGroup {
 }
 
 /// Contains information about a group's shout, if there is one.
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GroupShout {
     pub body: String,
-    pub poster: MinimalUserInfo,
+    pub poster: GroupMemberUser,
 }
 
 /// Contains all the information about a group that a certain user
 /// is in. Used as a component of [`UserGroup`] and in
 /// [`get_group_info`](crate::client::RustbloxClient::get_group_info).
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct GroupInfo {
     pub id: usize,
     pub name: String,
     pub description: String,
-    pub owner: MinimalUserInfo,
+    /// None if the group has no owner (i.e. it was abandoned).
+    pub owner: Option<GroupMemberUser>,
     pub shout: Option<GroupShout>,
     pub member_count: usize,
     pub is_builders_club_only: bool,
@@ -68,7 +69,7 @@ pub struct GroupInfo {
 
 /// Contains information about a user's role in a certain group. Used
 /// as a component of [`UserGroup`].
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct UserRoleInGroup {
     pub id: usize,
     pub name: String,
@@ -77,8 +78,45 @@ pub struct UserRoleInGroup {
 
 /// Contains information about a member of a group.
 /// Used in [`get_group_members`](crate::client::RustbloxClient::get_group_members)
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GroupMemberInfo {
-    pub user: MinimalUserInfo,
+    pub user: GroupMemberUser,
     pub role: GroupRole,
 }
+
+/// Represents a user as the groups API describes them. Used as a component of
+/// [`GroupMemberInfo`], [`JoinRequest`], [`GroupShout`] and [`GroupInfo`], and in
+/// [`get_group_role_members`](crate::client::RustbloxClient::get_group_role_members).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupMemberUser {
+    pub has_verified_badge: bool,
+    pub user_id: usize,
+    pub username: String,
+    pub display_name: String,
+}
+
+impl UserRef for GroupMemberUser {
+    fn id(&self) -> usize {
+        self.user_id
+    }
+
+    fn name(&self) -> &str {
+        &self.username
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+}
+
+impl From<GroupMemberUser> for MinimalUserInfo {
+    fn from(user: GroupMemberUser) -> Self {
+        Self {
+            id: user.user_id,
+            name: user.username,
+            display_name: user.display_name,
+            has_verified_badge: user.has_verified_badge,
+        }
+    }
+}
@@ -1,4 +1,9 @@
+pub mod auth;
+pub mod cloud;
 pub mod group;
+pub mod oauth;
+pub mod presence;
+pub mod twostepverification;
 pub mod user;
 
 /// Represents a sorting order for API endpoints
@@ -10,7 +15,7 @@ pub enum SortOrder {
 }
 
 /// Represents a page of data for paginated endpoints.
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct Page<T> {
     pub previous_page_cursor: Option<String>,
@@ -25,7 +30,7 @@ pub struct Page<T> {
 ///   "data": [ ... ]
 /// }
 /// ```
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DataWrapper<T> {
     pub data: Vec<T>,
 }
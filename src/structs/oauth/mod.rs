@@ -0,0 +1,62 @@
+use std::fmt::{Debug, Formatter};
+
+/// Contains a set of OAuth 2.0 tokens issued to an app.
+/// Used in [`exchange_oauth_code`](crate::client::RustbloxClient::exchange_oauth_code)
+/// and [`refresh_oauth_token`](crate::client::RustbloxClient::refresh_oauth_token).
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct OAuthToken {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub token_type: String,
+    pub expires_in: u64,
+    #[serde(default)]
+    pub scope: String,
+    pub id_token: Option<String>,
+}
+
+impl Debug for OAuthToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Don't leak the tokens into logs
+        f.debug_struct("OAuthToken")
+            .field("token_type", &self.token_type)
+            .field("expires_in", &self.expires_in)
+            .field("scope", &self.scope)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Contains information about the user that authorized an OAuth 2.0 app.
+/// Which fields are present depends on the scopes that were granted.
+/// Used in [`get_oauth_user_info`](crate::client::RustbloxClient::get_oauth_user_info).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OAuthUserInfo {
+    /// The user's ID, as a string.
+    pub sub: String,
+    pub name: Option<String>,
+    pub nickname: Option<String>,
+    pub preferred_username: Option<String>,
+    /// When the account was created, as a Unix timestamp (in seconds).
+    pub created_at: Option<u64>,
+    pub profile: Option<String>,
+    pub picture: Option<String>,
+}
+
+/// Contains information about an OAuth 2.0 access or refresh token.
+/// Inactive (expired or revoked) tokens only have `active` set.
+/// Used in [`introspect_oauth_token`](crate::client::RustbloxClient::introspect_oauth_token).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OAuthTokenIntrospection {
+    pub active: bool,
+    pub jti: Option<String>,
+    pub iss: Option<String>,
+    pub token_type: Option<String>,
+    pub client_id: Option<String>,
+    pub aud: Option<String>,
+    /// The ID of the user that authorized the token, as a string.
+    pub sub: Option<String>,
+    pub scope: Option<String>,
+    /// When the token expires, as a Unix timestamp (in seconds).
+    pub exp: Option<u64>,
+    /// When the token was issued, as a Unix timestamp (in seconds).
+    pub iat: Option<u64>,
+}
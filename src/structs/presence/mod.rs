@@ -0,0 +1,101 @@
+/// What a user is doing on Roblox. Roblox sends it as a number (0 to 4), which
+/// this parses from and serializes back to.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum PresenceType {
+    Offline,
+    /// Online, but only on the website or app.
+    Website,
+    InGame,
+    InStudio,
+    /// Online, but hiding it. Only the user themselves sees this.
+    Invisible,
+}
+
+impl From<PresenceType> for u8 {
+    fn from(presence: PresenceType) -> Self {
+        match presence {
+            PresenceType::Offline => 0,
+            PresenceType::Website => 1,
+            PresenceType::InGame => 2,
+            PresenceType::InStudio => 3,
+            PresenceType::Invisible => 4,
+        }
+    }
+}
+
+impl TryFrom<u8> for PresenceType {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Offline),
+            1 => Ok(Self::Website),
+            2 => Ok(Self::InGame),
+            3 => Ok(Self::InStudio),
+            4 => Ok(Self::Invisible),
+            _ => Err(format!("{value} is not a known presence type")),
+        }
+    }
+}
+
+/// Contains a user's presence. The place, universe and job IDs are only there
+/// when the user is in a game (or Studio) and their privacy settings let the
+/// authenticated user see it.
+/// Used in [`get_user_presences`](crate::client::RustbloxClient::get_user_presences).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct UserPresence {
+    pub user_id: usize,
+    pub user_presence_type: PresenceType,
+    /// Where Roblox last saw the user (i.e. `Website` or the name of a game).
+    pub last_location: String,
+    pub place_id: Option<u64>,
+    pub root_place_id: Option<u64>,
+    pub universe_id: Option<u64>,
+    /// The ID of the server the user is in. Roblox calls this `gameId`.
+    #[serde(rename = "gameId")]
+    pub job_id: Option<String>,
+    /// None if Roblox left it out, which it does for users whose presence is hidden.
+    pub last_online: Option<String>,
+}
+
+impl UserPresence {
+    /// Returns whether the user is doing the same thing in both presences: same
+    /// presence type, place, universe and server. The last online time is ignored,
+    /// since it moves along on its own while the user is online.
+    #[must_use]
+    pub fn same_activity(&self, other: &Self) -> bool {
+        self.user_presence_type == other.user_presence_type
+            && self.last_location == other.last_location
+            && self.place_id == other.place_id
+            && self.root_place_id == other.root_place_id
+            && self.universe_id == other.universe_id
+            && self.job_id == other.job_id
+    }
+}
+
+/// Contains the presences of a batch of users.
+/// Used in [`get_user_presences`](crate::client::RustbloxClient::get_user_presences).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct UserPresences {
+    pub user_presences: Vec<UserPresence>,
+}
+
+/// Contains when a user was last online.
+/// Used in [`get_users_last_online`](crate::client::RustbloxClient::get_users_last_online).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct LastOnline {
+    pub user_id: usize,
+    pub last_online: String,
+}
+
+/// Contains when a batch of users were last online.
+/// Used in [`get_users_last_online`](crate::client::RustbloxClient::get_users_last_online).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct LastOnlineTimestamps {
+    pub last_online_timestamps: Vec<LastOnline>,
+}
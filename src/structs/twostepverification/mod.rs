@@ -0,0 +1,18 @@
+/// The metadata Roblox sends along with a two-step verification challenge,
+/// decoded from the `rblx-challenge-metadata` header.
+/// Used by [`TwoStepVerificationSolver`](crate::twostepverification::TwoStepVerificationSolver).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoStepVerificationMetadata {
+    pub user_id: String,
+    pub challenge_id: String,
+    pub action_type: String,
+}
+
+/// Contains the token handed out after a two-step verification code is accepted.
+/// Used in [`verify_authenticator_code`](crate::client::RustbloxClient::verify_authenticator_code).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoStepVerificationToken {
+    pub verification_token: String,
+}
@@ -1,7 +1,7 @@
 /// Contains the expanded set of user info provided by
 /// the Roblox API. Used primarily by
 /// [`get_user_info`](crate::client::RustbloxClient::get_user_info).
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct UserInfo {
     pub description: String,
@@ -16,7 +16,7 @@ pub struct UserInfo {
 
 /// Contains minimal information about the authenticated user.
 /// Used in [`get_authenticated_user`](crate::client::RustbloxClient::get_authenticated_user).
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct MinimalAuthenticatedUser {
     pub id: usize,
@@ -26,7 +26,7 @@ pub struct MinimalAuthenticatedUser {
 
 /// Contains information about the authenticated user's age bracket.
 /// Used in [`get_authenticated_user_age_bracket`](crate::client::RustbloxClient::get_authenticated_user_age_bracket).
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthenticatedUserAgeBracket {
     pub age_bracket: usize,
@@ -34,7 +34,7 @@ pub struct AuthenticatedUserAgeBracket {
 
 /// Contains information about the authenticated user's country code.
 /// Used in [`get_authenticated_user_country_code`](crate::client::RustbloxClient::get_authenticated_user_country_code).
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthenticatedUserCountryCode {
     pub country_code: String,
@@ -42,37 +42,279 @@ pub struct AuthenticatedUserCountryCode {
 
 /// Contains information about the authenticated user's country code.
 /// Used in [`get_authenticated_user_roles`](crate::client::RustbloxClient::get_authenticated_user_roles).
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AuthenticatedUserRoles {
     pub roles: Vec<String>,
 }
 
-/// Contains a minimal set of user info provided by
-/// the Roblox API. Used throughout a variety of functions
-/// whenever a small amount of user info is returned as part of
-/// a larger body.
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+/// Contains how many Robux a user has.
+/// Used in [`get_user_robux`](crate::client::RustbloxClient::get_user_robux).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UserCurrency {
+    pub robux: u64,
+}
+
+/// Contains the authenticated user's profile description.
+/// Used in [`get_user_description`](crate::client::RustbloxClient::get_user_description)
+/// and [`set_authenticated_user_description`](crate::client::RustbloxClient::set_authenticated_user_description).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UserDescription {
+    pub description: String,
+}
+
+/// Represents a birthdate.
+/// Used in [`get_authenticated_user_birthdate`](crate::client::RustbloxClient::get_authenticated_user_birthdate)
+/// and [`set_authenticated_user_birthdate`](crate::client::RustbloxClient::set_authenticated_user_birthdate).
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Birthdate {
+    pub birth_month: u8,
+    pub birth_day: u8,
+    pub birth_year: u16,
+}
+
+/// Represents the gender on a Roblox account. Roblox sends it as a number
+/// (1, 2 or 3), which this parses from and serializes back to.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum Gender {
+    Unknown,
+    Male,
+    Female,
+}
+
+impl Gender {
+    /// Returns the name Roblox uses for this gender (i.e. `Female`).
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unknown => "Unknown",
+            Self::Male => "Male",
+            Self::Female => "Female",
+        }
+    }
+}
+
+impl From<Gender> for u8 {
+    fn from(gender: Gender) -> Self {
+        match gender {
+            Gender::Unknown => 1,
+            Gender::Male => 2,
+            Gender::Female => 3,
+        }
+    }
+}
+
+impl TryFrom<u8> for Gender {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::Unknown),
+            2 => Ok(Self::Male),
+            3 => Ok(Self::Female),
+            _ => Err(format!("{value} is not a known gender")),
+        }
+    }
+}
+
+/// Contains the authenticated user's gender.
+/// Used in [`get_authenticated_user_gender`](crate::client::RustbloxClient::get_authenticated_user_gender).
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct AuthenticatedUserGender {
+    pub gender: Gender,
+}
+
+/// Anything that refers to a single Roblox user. Every user type Rustblox
+/// returns implements this, so code that only needs to know who someone is
+/// doesn't have to care which endpoint they came from.
+pub trait UserRef {
+    /// The user's ID.
+    fn id(&self) -> usize;
+    /// The user's username.
+    fn name(&self) -> &str;
+    /// The user's display name.
+    fn display_name(&self) -> &str;
+}
+
+/// Contains the information that every endpoint returning a user agrees on.
+/// Any of the per-endpoint user types can be turned into one with [`From`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct MinimalUserInfo {
+    pub id: usize,
+    pub name: String,
     pub display_name: String,
     pub has_verified_badge: bool,
-    #[serde(alias = "userId")]
+}
+
+/// Represents a user found by their ID.
+/// Used in [`get_users_from_ids`](crate::client::RustbloxClient::get_users_from_ids).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct UserIdLookupResult {
+    pub has_verified_badge: bool,
     pub id: usize,
-    #[serde(alias = "username")]
     pub name: String,
-    /// Used in [`get_users_from_usernames`](crate::client::RustbloxClient::get_users_from_usernames).
-    pub requested_username: Option<String>,
-    /// Used in [`search_user`](crate::client::RustbloxClient::search_user).
-    pub previous_usernames: Option<Vec<String>>,
-}
-// I love the #[serde(alias)] macro since, for some reason, Roblox
-// decided to change the names of certain JSON objects
-// DESPITE THEM LITERALLY HAVING THE SAME DATA.
-
-/// Represents a user's previous username. Used as a component
-/// Used primarily in [`get_previous_usernames`](crate::client::RustbloxClient::get_previous_usernames).
-#[allow(dead_code, non_snake_case)]
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub display_name: String,
+}
+
+/// Represents a user found by their username. `requested_username` is the
+/// username that was looked up, which may be one of the user's previous usernames.
+/// Used in [`get_users_from_usernames`](crate::client::RustbloxClient::get_users_from_usernames).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct UsernameLookupResult {
+    pub requested_username: String,
+    pub has_verified_badge: bool,
+    pub id: usize,
+    pub name: String,
+    pub display_name: String,
+}
+
+/// Represents a user found by a keyword search.
+/// Used in [`search_user`](crate::client::RustbloxClient::search_user).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct UserSearchResult {
+    pub previous_usernames: Vec<String>,
+    pub has_verified_badge: bool,
+    pub id: usize,
+    pub name: String,
+    pub display_name: String,
+}
+
+impl UserRef for UserInfo {
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+}
+
+impl UserRef for MinimalAuthenticatedUser {
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+}
+
+impl UserRef for MinimalUserInfo {
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+}
+
+impl UserRef for UserIdLookupResult {
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+}
+
+impl UserRef for UsernameLookupResult {
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+}
+
+impl UserRef for UserSearchResult {
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+}
+
+impl From<UserInfo> for MinimalUserInfo {
+    fn from(user: UserInfo) -> Self {
+        Self {
+            id: user.id,
+            name: user.name,
+            display_name: user.display_name,
+            has_verified_badge: user.has_verified_badge,
+        }
+    }
+}
+
+impl From<UserIdLookupResult> for MinimalUserInfo {
+    fn from(user: UserIdLookupResult) -> Self {
+        Self {
+            id: user.id,
+            name: user.name,
+            display_name: user.display_name,
+            has_verified_badge: user.has_verified_badge,
+        }
+    }
+}
+
+impl From<UsernameLookupResult> for MinimalUserInfo {
+    fn from(user: UsernameLookupResult) -> Self {
+        Self {
+            id: user.id,
+            name: user.name,
+            display_name: user.display_name,
+            has_verified_badge: user.has_verified_badge,
+        }
+    }
+}
+
+impl From<UserSearchResult> for MinimalUserInfo {
+    fn from(user: UserSearchResult) -> Self {
+        Self {
+            id: user.id,
+            name: user.name,
+            display_name: user.display_name,
+            has_verified_badge: user.has_verified_badge,
+        }
+    }
+}
+
+/// Represents a user's previous username.
+/// Used in [`get_previous_usernames`](crate::client::RustbloxClient::get_previous_usernames).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PreviousUsername {
-    name: String,
+    pub name: String,
 }
@@ -0,0 +1,84 @@
+use super::{mock_response, mock_server};
+use crate::client::builder::RustbloxClientBuilder;
+use crate::client::{AccountSession, Method};
+use crate::error::ClientError;
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+async fn as_account_uses_session_credentials() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = Arc::clone(&seen);
+    let url = mock_server(move |request| {
+        let cookie = request.header("cookie").unwrap_or_default().to_string();
+        let csrf = request.header("x-csrf-token").map(String::from);
+        seen_clone
+            .lock()
+            .unwrap()
+            .push((cookie.clone(), csrf.clone()));
+
+        match csrf {
+            Some(_) => mock_response(200, &[], "{}"),
+            // Hand out a token tied to the cookie, like Roblox does
+            None => mock_response(
+                403,
+                &[("x-csrf-token", &format!("token-for-{cookie}"))],
+                r#"{"errors":[{"code":0,"message":"Token Validation Failed"}]}"#,
+            ),
+        }
+    });
+
+    let client = RustbloxClientBuilder::new()
+        .insert_cookie("_|WARNING:main")
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = AccountSession::new("_|WARNING:bot").unwrap();
+
+    let result = client
+        .as_account(&session)
+        .request_json(Method::POST, url.as_str())
+        .authenticated(true)
+        .send()
+        .await;
+    assert!(result.is_ok());
+
+    // The session keeps its token, and the client's own credentials are untouched
+    let bot_cookie = ".ROBLOSECURITY=_|WARNING:bot".to_string();
+    assert_eq!(
+        client.as_account(&session).csrf_token().await,
+        Some(format!("token-for-{bot_cookie}"))
+    );
+    assert_eq!(client.csrf_token().await, None);
+    assert_eq!(
+        client.roblox_cookie(),
+        Some(".ROBLOSECURITY=_|WARNING:main".to_string())
+    );
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![
+            (bot_cookie.clone(), None),
+            (bot_cookie.clone(), Some(format!("token-for-{bot_cookie}"))),
+        ]
+    );
+
+    // Cookie updates through the view land in the session
+    client
+        .as_account(&session)
+        .set_roblox_cookie("_|WARNING:bot-2")
+        .await
+        .unwrap();
+    assert_eq!(
+        session.roblox_cookie(),
+        Some(".ROBLOSECURITY=_|WARNING:bot-2".to_string())
+    );
+    assert_eq!(
+        client.roblox_cookie(),
+        Some(".ROBLOSECURITY=_|WARNING:main".to_string())
+    );
+}
+
+#[test]
+fn invalid_session_cookie() {
+    let session = AccountSession::new("not-a-cookie");
+    assert!(matches!(session, Err(ClientError::InvalidCookie)));
+}
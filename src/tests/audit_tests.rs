@@ -0,0 +1,181 @@
+use super::{mock_response, mock_server};
+use crate::audit::{AuditEntry, AuditOutcome, AuditSink, JsonLinesAuditSink};
+use crate::client::builder::RustbloxClientBuilder;
+use crate::client::{AuthMethod, Method};
+use crate::endpoint::Endpoint;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct MemorySink(Arc<Mutex<Vec<AuditEntry>>>);
+
+#[async_trait::async_trait]
+impl AuditSink for MemorySink {
+    async fn record(
+        &self,
+        entry: &AuditEntry,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.0.lock().unwrap().push(entry.clone());
+        Ok(())
+    }
+}
+
+struct MockMutation {
+    url: String,
+    method: Method,
+}
+
+impl Endpoint for MockMutation {
+    type Body = ();
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        self.method.clone()
+    }
+
+    fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::None
+    }
+
+    fn mutating(&self) -> bool {
+        self.method != Method::GET
+    }
+
+    fn audit_targets(&self) -> Vec<(&'static str, serde_json::Value)> {
+        vec![("group_id", 7.into())]
+    }
+}
+
+#[tokio::test]
+async fn dry_run_kick_is_recorded() {
+    let sink = MemorySink::default();
+    let client = RustbloxClientBuilder::new()
+        .insert_cookie("_|WARNING:fake-cookie")
+        .unwrap()
+        .dry_run(true)
+        .audit_sink(sink.clone())
+        .build()
+        .unwrap();
+    assert!(client.is_audited());
+
+    client.kick_user(1, 2).await.unwrap();
+
+    let entries = sink.0.lock().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].action, "KickUser");
+    assert_eq!(entries[0].outcome, AuditOutcome::DryRun);
+    assert_eq!(
+        serde_json::Value::Object(entries[0].targets.clone()),
+        serde_json::json!({ "group_id": 1, "user_id": 2 })
+    );
+}
+
+struct MockLookup {
+    url: String,
+}
+
+impl Endpoint for MockLookup {
+    type Body = ();
+    type Response = serde_json::Value;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    fn auth(&self) -> AuthMethod {
+        AuthMethod::None
+    }
+
+    fn mutating(&self) -> bool {
+        false
+    }
+}
+
+#[tokio::test]
+async fn read_only_posts_are_recorded() {
+    let url = mock_server(|_| mock_response(200, &[], "{}"));
+    let sink = MemorySink::default();
+    let client = RustbloxClientBuilder::new()
+        .dry_run(true)
+        .audit_sink(sink.clone())
+        .build()
+        .unwrap();
+
+    client.execute(MockLookup { url }).await.unwrap();
+
+    let entries = sink.0.lock().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].action, "MockLookup");
+    // It isn't mutating, so it was sent even though the client is in dry-run mode
+    assert_eq!(entries[0].outcome, AuditOutcome::Succeeded);
+}
+
+#[tokio::test]
+async fn failures_are_written_as_json_lines() {
+    let url = mock_server(|request| match request.method.as_str() {
+        "GET" => mock_response(200, &[], "{}"),
+        _ => mock_response(
+            400,
+            &[],
+            r#"{"errors":[{"code":3,"message":"The user is invalid or does not exist."}]}"#,
+        ),
+    });
+    let path = std::env::temp_dir().join(format!("rustblox-audit-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let client = RustbloxClientBuilder::new()
+        .audit_sink(JsonLinesAuditSink::new(&path))
+        .build()
+        .unwrap();
+
+    let read = client
+        .execute(MockMutation {
+            url: url.clone(),
+            method: Method::GET,
+        })
+        .await;
+    assert!(read.is_ok());
+    for _ in 0..2 {
+        let write = client
+            .execute(MockMutation {
+                url: url.clone(),
+                method: Method::DELETE,
+            })
+            .await;
+        assert!(write.is_err());
+    }
+
+    let journal = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let entries = journal
+        .lines()
+        .map(|line| serde_json::from_str::<AuditEntry>(line).unwrap())
+        .collect::<Vec<_>>();
+
+    // Only the calls that don't use GET are recorded
+    assert_eq!(entries.len(), 2);
+    for entry in entries {
+        assert_eq!(entry.action, "MockMutation");
+        assert_eq!(entry.actor_id, None);
+        assert_eq!(entry.targets.get("group_id"), Some(&serde_json::json!(7)));
+        assert!(matches!(entry.outcome, AuditOutcome::Failed(ref why) if why.contains("400")));
+    }
+}
+
+#[test]
+fn outcome_serialization() {
+    assert_eq!(
+        serde_json::to_value(AuditOutcome::Failed("oops".to_string())).unwrap(),
+        serde_json::json!({ "status": "failed", "error": "oops" })
+    );
+    assert_eq!(
+        serde_json::to_value(AuditOutcome::Succeeded).unwrap(),
+        serde_json::json!({ "status": "succeeded" })
+    );
+}
@@ -1,19 +1,34 @@
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 
-use super::get_cookie;
-use crate::client::{RequestComponents, RustbloxClient};
-use reqwest::{
-    header::{HeaderMap, HeaderValue},
-    Method,
-};
+use super::{get_cookie, mock_response, mock_server};
+use crate::challenge::CHALLENGE_CONTINUE_URL;
+use crate::client::builder::RustbloxClientBuilder;
+use crate::client::{Method, RustbloxClient, CSRF_URL};
+use crate::credentials::AuthEvent;
+use crate::error::RequestError;
 
 #[tokio::test]
 async fn bad_cookie_test() {
     let client = RustbloxClient {
         reqwest_client: Default::default(),
-        roblox_cookie: Some("_|WARNING:bad-cookie".to_string()),
-        csrf_token: Arc::new(RwLock::new(Some("bad-token".to_string()))),
+        roblox_cookie: Arc::new(RwLock::new(Some("_|WARNING:bad-cookie".to_string()))),
+        csrf_token: Arc::new(tokio::sync::RwLock::new(Some("bad-token".to_string()))),
+        api_key: None,
+        oauth: None,
         auto_reauth: true,
+        validate_names: true,
+        lazy_csrf: false,
+        dry_run: false,
+        challenge_solver: None,
+        credential_provider: None,
+        auth_callback: None,
+        audit_sink: None,
+        detect_drift: false,
+        drift_callback: None,
+        actor_ids: Default::default(),
+        csrf_url: CSRF_URL.to_string(),
+        challenge_continue_url: CHALLENGE_CONTINUE_URL.to_string(),
     };
 
     let result = client.batch_get_requests(1).await;
@@ -25,31 +40,134 @@ async fn bad_cookie_test() {
 async fn bad_token_refresh() {
     let client = RustbloxClient {
         reqwest_client: Default::default(),
-        roblox_cookie: Some(format!(".ROBLOSECURITY={}", get_cookie())),
-        csrf_token: Arc::new(RwLock::new(Some("bad-token".to_string()))),
+        roblox_cookie: Arc::new(RwLock::new(Some(format!(
+            ".ROBLOSECURITY={}",
+            get_cookie()
+        )))),
+        csrf_token: Arc::new(tokio::sync::RwLock::new(Some("bad-token".to_string()))),
+        api_key: None,
+        oauth: None,
         auto_reauth: true,
+        validate_names: true,
+        lazy_csrf: false,
+        dry_run: false,
+        challenge_solver: None,
+        credential_provider: None,
+        auth_callback: None,
+        audit_sink: None,
+        detect_drift: false,
+        drift_callback: None,
+        actor_ids: Default::default(),
+        csrf_url: CSRF_URL.to_string(),
+        challenge_continue_url: CHALLENGE_CONTINUE_URL.to_string(),
     };
 
-    // Some manual stuff because I don't plan on supporting this endpoint
-    // aside from this test
-    let body = serde_json::json!({ "description": "testing" }).to_string();
-    let mut headers = HeaderMap::new();
-    headers.append("Content-Type", HeaderValue::from_static("application/json"));
-    headers.append(
-        "Content-Length",
-        HeaderValue::from_str(&body.as_bytes().len().to_string()).unwrap(),
-    );
-
-    let components = RequestComponents {
-        needs_auth: true,
-        method: Method::POST,
-        url: "https://users.roblox.com/v1/description".into(),
-        headers: Some(headers),
-        body: Some(body),
-    };
+    let result = client.set_authenticated_user_description("testing").await;
+    println!("{:#?}", result);
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn lazy_token_missing_does_not_panic() {
+    let client = RustbloxClientBuilder::new()
+        .insert_cookie("_|WARNING:fake-cookie")
+        .unwrap()
+        .lazy_csrf(true)
+        .build()
+        .unwrap();
+    assert!(client.is_authenticated().await);
+    assert!(client.csrf_token().await.is_none());
+
+    // Nothing listens on the discard port, so this fails before reaching Roblox
     let result = client
-        .make_request::<serde_json::Value>(components, false)
+        .request_json(Method::POST, "http://127.0.0.1:9/")
+        .authenticated(true)
+        .send()
         .await;
+    assert!(matches!(result, Err(RequestError::RequestError(..))));
+}
+
+#[tokio::test]
+async fn lazy_token_refresh() {
+    let client = RustbloxClientBuilder::new()
+        .insert_cookie(&get_cookie())
+        .unwrap()
+        .lazy_csrf(true)
+        .build()
+        .unwrap();
+    client.login().await.unwrap();
+    assert!(client.csrf_token().await.is_none());
+
+    let result = client.set_authenticated_user_description("testing").await;
     println!("{:#?}", result);
     assert!(result.is_ok());
+    assert!(client.csrf_token().await.is_some());
+}
+
+/// Sends six write requests at once with a stale `x-csrf-token`. Rejections come with a
+/// fresh token in their headers if `offer_token` is set; otherwise the client has to fetch
+/// one. Returns how many token fetches the mock server saw.
+async fn token_burst(offer_token: bool) -> usize {
+    let fetches = Arc::new(AtomicUsize::new(0));
+    let fetches_clone = Arc::clone(&fetches);
+    let url = mock_server(move |request| {
+        assert_eq!(request.method, "POST");
+        if request.path == "/csrf" {
+            fetches_clone.fetch_add(1, Ordering::SeqCst);
+            return mock_response(403, &[("x-csrf-token", "fresh-token")], "");
+        }
+        if request.header("x-csrf-token") == Some("fresh-token") {
+            return mock_response(200, &[], "{}");
+        }
+        let headers: &[(&str, &str)] = if offer_token {
+            &[("x-csrf-token", "fresh-token")]
+        } else {
+            &[]
+        };
+        mock_response(
+            403,
+            headers,
+            r#"{"errors":[{"code":0,"message":"Token Validation Failed"}]}"#,
+        )
+    });
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_clone = Arc::clone(&events);
+    let mut client = RustbloxClientBuilder::new()
+        .insert_cookie("_|WARNING:fake-cookie")
+        .unwrap()
+        .on_auth_event(move |event| events_clone.lock().unwrap().push(event))
+        .build()
+        .unwrap();
+    client.csrf_url = format!("{url}/csrf");
+    *client.csrf_token.write().await = Some("stale-token".to_string());
+
+    let send = || {
+        client
+            .request_json(Method::POST, url.as_str())
+            .authenticated(true)
+            .send()
+    };
+    let results = tokio::join!(send(), send(), send(), send(), send(), send());
+    for result in [
+        results.0, results.1, results.2, results.3, results.4, results.5,
+    ] {
+        assert!(result.is_ok(), "{result:?}");
+    }
+
+    assert_eq!(client.csrf_token().await, Some("fresh-token".to_string()));
+    // Every request can get a 403, but only one of them replaces the token
+    assert_eq!(*events.lock().unwrap(), vec![AuthEvent::Reauthenticated]);
+    fetches.load(Ordering::SeqCst)
+}
+
+#[tokio::test]
+async fn single_flight_token_refresh() {
+    // The token comes with the 403, so nothing needs fetching
+    assert_eq!(token_burst(true).await, 0);
+}
+
+#[tokio::test]
+async fn single_flight_token_fetch() {
+    assert_eq!(token_burst(false).await, 1);
 }
@@ -0,0 +1,198 @@
+use super::{mock_response, mock_server, MockRequest};
+use crate::challenge::{Challenge, ChallengeSolution, ChallengeSolver, ChallengeType};
+use crate::client::builder::RustbloxClientBuilder;
+use crate::client::{AuthMethod, Method, RustbloxClient};
+use crate::error::RequestError;
+use reqwest::header::{HeaderMap, HeaderValue};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+fn challenge_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("rblx-challenge-id", HeaderValue::from_static("abc-123"));
+    headers.insert(
+        "rblx-challenge-type",
+        HeaderValue::from_static("twostepverification"),
+    );
+    // {"userId":"1","challengeId":"def-456","actionType":"Generic"}
+    headers.insert(
+        "rblx-challenge-metadata",
+        HeaderValue::from_static(
+            "eyJ1c2VySWQiOiIxIiwiY2hhbGxlbmdlSWQiOiJkZWYtNDU2IiwiYWN0aW9uVHlwZSI6IkdlbmVyaWMifQ==",
+        ),
+    );
+    headers
+}
+
+#[test]
+fn parse_challenge_headers() {
+    let challenge = Challenge::from_headers(&challenge_headers()).unwrap();
+    assert_eq!(challenge.id, "abc-123");
+    assert_eq!(challenge.challenge_type, ChallengeType::TwoStepVerification);
+
+    let metadata = challenge.metadata_json().unwrap();
+    assert_eq!(metadata["challengeId"], "def-456");
+    assert_eq!(metadata["actionType"], "Generic");
+
+    assert!(Challenge::from_headers(&HeaderMap::new()).is_none());
+    assert_eq!(
+        ChallengeType::from("somethingnew"),
+        ChallengeType::Other("somethingnew".to_string())
+    );
+}
+
+#[test]
+fn solution_headers() {
+    let challenge = Challenge::from_headers(&challenge_headers()).unwrap();
+    let solution = ChallengeSolution::new(
+        &challenge,
+        serde_json::json!({ "verificationToken": "token" }),
+    );
+    let headers = solution.headers().unwrap();
+    assert_eq!(headers["rblx-challenge-id"], "abc-123");
+    assert_eq!(headers["rblx-challenge-type"], "twostepverification");
+
+    let echoed = Challenge::from_headers(&headers).unwrap();
+    assert_eq!(
+        echoed.metadata_json().unwrap(),
+        serde_json::json!({ "verificationToken": "token" })
+    );
+}
+
+struct PassingSolver;
+
+#[async_trait::async_trait]
+impl ChallengeSolver for PassingSolver {
+    async fn solve(
+        &self,
+        _client: &RustbloxClient,
+        _challenge: &Challenge,
+    ) -> Result<Option<ChallengeSolution>, RequestError> {
+        Ok(None)
+    }
+}
+
+#[derive(Default)]
+struct TokenSolver(Arc<AtomicUsize>);
+
+#[async_trait::async_trait]
+impl ChallengeSolver for TokenSolver {
+    async fn solve(
+        &self,
+        _client: &RustbloxClient,
+        challenge: &Challenge,
+    ) -> Result<Option<ChallengeSolution>, RequestError> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Ok(Some(ChallengeSolution::new(
+            challenge,
+            serde_json::json!({ "verificationToken": "token" }),
+        )))
+    }
+}
+
+/// Sends an API key request to a mock server that challenges `/action`, and returns the
+/// result along with every request the server got. Requests carrying a solution are let
+/// through unless `challenge_retries` is set.
+async fn challenged_request(
+    solver: impl ChallengeSolver + 'static,
+    challenge_retries: bool,
+) -> (Result<serde_json::Value, RequestError>, Vec<MockRequest>) {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let requests_clone = Arc::clone(&requests);
+    let url = mock_server(move |request| {
+        requests_clone.lock().unwrap().push(request.clone());
+        match request.path.as_str() {
+            "/continue" => mock_response(200, &[], "{}"),
+            _ if request.header("rblx-challenge-id").is_some() && !challenge_retries => {
+                mock_response(200, &[], r#"{"ok":true}"#)
+            }
+            _ => mock_response(
+                403,
+                &[
+                    ("rblx-challenge-id", "abc-123"),
+                    ("rblx-challenge-type", "twostepverification"),
+                    ("rblx-challenge-metadata", "e30="),
+                ],
+                r#"{"errors":[{"code":0,"message":"Challenge is required to authorize the request"}]}"#,
+            ),
+        }
+    });
+    let mut client = RustbloxClientBuilder::new()
+        .insert_cookie("_|WARNING:fake-cookie")
+        .unwrap()
+        .api_key("fake-key")
+        .challenge_solver(solver)
+        .build()
+        .unwrap();
+    client.challenge_continue_url = format!("{url}/continue");
+
+    let result = client
+        .request_json(Method::POST, format!("{url}/action"))
+        .auth(AuthMethod::ApiKey)
+        .send()
+        .await;
+    let requests = requests.lock().unwrap().clone();
+    (result, requests)
+}
+
+#[tokio::test]
+async fn solved_challenges_are_continued_and_resent() {
+    let solves = Arc::new(AtomicUsize::new(0));
+    let (result, requests) = challenged_request(TokenSolver(Arc::clone(&solves)), false).await;
+
+    assert_eq!(result.unwrap(), serde_json::json!({ "ok": true }));
+    assert_eq!(solves.load(Ordering::SeqCst), 1);
+    let paths = requests.iter().map(|r| r.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(paths, ["/action", "/continue", "/action"]);
+
+    // The continue call goes out with the same credentials as the challenged request
+    let continued = &requests[1];
+    assert_eq!(continued.header("x-api-key"), Some("fake-key"));
+    assert_eq!(continued.header("cookie"), None);
+    let body = serde_json::from_str::<serde_json::Value>(&continued.body).unwrap();
+    assert_eq!(body["challengeId"], "abc-123");
+    assert_eq!(body["challengeType"], "twostepverification");
+    assert_eq!(
+        body["challengeMetadata"],
+        r#"{"verificationToken":"token"}"#
+    );
+
+    assert_eq!(requests[0].header("rblx-challenge-id"), None);
+    let resent = &requests[2];
+    assert_eq!(resent.header("rblx-challenge-id"), Some("abc-123"));
+    assert_eq!(
+        resent.header("rblx-challenge-type"),
+        Some("twostepverification")
+    );
+    // {"verificationToken":"token"}
+    assert_eq!(
+        resent.header("rblx-challenge-metadata"),
+        Some("eyJ2ZXJpZmljYXRpb25Ub2tlbiI6InRva2VuIn0=")
+    );
+    assert_eq!(resent.header("x-api-key"), Some("fake-key"));
+}
+
+#[tokio::test]
+async fn challenged_retries_are_not_solved_again() {
+    let solves = Arc::new(AtomicUsize::new(0));
+    let (result, requests) = challenged_request(TokenSolver(Arc::clone(&solves)), true).await;
+
+    match result {
+        Err(RequestError::ChallengeRequired(url, challenge)) => {
+            assert!(url.ends_with("/action"));
+            assert_eq!(challenge.id, "abc-123");
+        }
+        other => panic!("Expected ChallengeRequired, got {other:?}"),
+    }
+    assert_eq!(solves.load(Ordering::SeqCst), 1);
+    let paths = requests.iter().map(|r| r.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(paths, ["/action", "/continue", "/action"]);
+}
+
+#[tokio::test]
+async fn passed_challenges_are_not_continued() {
+    let (result, requests) = challenged_request(PassingSolver, false).await;
+
+    assert!(matches!(result, Err(RequestError::ChallengeRequired(..))));
+    assert_eq!(requests.len(), 1);
+}
@@ -1,5 +1,7 @@
 use super::get_cookie;
 use crate::client::builder::RustbloxClientBuilder;
+use crate::client::RustbloxClient;
+use crate::error::ClientError;
 
 #[test]
 fn build_client() {
@@ -29,3 +31,32 @@ async fn build_and_login() {
 
     assert!(login.is_ok());
 }
+
+#[tokio::test]
+async fn clones_share_credentials() {
+    fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+    assert_shareable::<RustbloxClient>();
+
+    let client = RustbloxClientBuilder::new()
+        .insert_cookie("_|WARNING:old")
+        .unwrap()
+        .build()
+        .unwrap();
+    *client.csrf_token.write().await = Some("token".to_string());
+
+    let clone = client.clone();
+    let task = tokio::spawn(async move {
+        clone.set_roblox_cookie("_|WARNING:new").await.unwrap();
+        clone
+    });
+    let clone = task.await.unwrap();
+
+    let expected = Some(".ROBLOSECURITY=_|WARNING:new".to_string());
+    assert_eq!(client.roblox_cookie(), expected);
+    assert_eq!(clone.roblox_cookie(), expected);
+    assert_eq!(client.csrf_token().await, None);
+
+    let result = client.set_roblox_cookie("not-a-cookie").await;
+    assert!(matches!(result, Err(ClientError::InvalidCookie)));
+    assert_eq!(clone.roblox_cookie(), expected);
+}
@@ -0,0 +1,52 @@
+use super::get_api_key;
+use crate::client::builder::RustbloxClientBuilder;
+use crate::client::{AuthMethod, Method};
+use crate::error::RequestError;
+use crate::structs::cloud::ApiKeyInfo;
+
+#[tokio::test]
+async fn api_key_required() {
+    let client = RustbloxClientBuilder::new().build().unwrap();
+    let result = client.introspect_api_key().await;
+    assert!(matches!(result, Err(RequestError::NotAuthenticated)));
+
+    let result = client
+        .request_json(Method::GET, "https://apis.roblox.com/cloud/v2/users/1")
+        .auth(AuthMethod::ApiKey)
+        .send()
+        .await;
+    assert!(matches!(result, Err(RequestError::NotAuthenticated)));
+}
+
+#[test]
+fn api_key_scopes() {
+    let info = serde_json::from_value::<ApiKeyInfo>(serde_json::json!({
+        "name": "Ranking bot",
+        "authorizedUserId": 1,
+        "scopes": [
+            {
+                "name": "group:read",
+                "operations": ["read"]
+            }
+        ],
+        "enabled": true,
+        "expired": false,
+        "expirationTimeUtc": null
+    }))
+    .unwrap();
+
+    assert!(info.allows("group:read", "read"));
+    assert!(!info.allows("group:read", "write"));
+    assert!(!info.allows("group:write", "read"));
+}
+
+#[tokio::test]
+async fn introspect_api_key() {
+    let client = RustbloxClientBuilder::new()
+        .api_key(&get_api_key())
+        .build()
+        .unwrap();
+    let result = client.introspect_api_key().await;
+    println!("{:#?}", result);
+    assert!(result.is_ok());
+}
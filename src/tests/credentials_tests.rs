@@ -0,0 +1,167 @@
+use super::{mock_response, mock_server};
+use crate::client::builder::RustbloxClientBuilder;
+use crate::client::Method;
+use crate::credentials::{AuthEvent, CookieFile, CredentialProvider};
+use crate::error::RequestError;
+use std::sync::{Arc, Mutex};
+
+struct FixedCookie(&'static str);
+
+#[async_trait::async_trait]
+impl CredentialProvider for FixedCookie {
+    async fn cookie(&self) -> Result<Option<String>, RequestError> {
+        Ok(Some(self.0.to_string()))
+    }
+}
+
+/// Answers with `statuses` in order and records the `Cookie` header of every request.
+fn serve(statuses: Vec<u16>) -> (String, Arc<Mutex<Vec<String>>>) {
+    let cookies = Arc::new(Mutex::new(Vec::new()));
+    let cookies_clone = Arc::clone(&cookies);
+    let url = mock_server(move |request| {
+        assert_eq!(request.path, "/");
+        let mut cookies = cookies_clone.lock().unwrap();
+        cookies.push(request.header("cookie").unwrap_or_default().to_string());
+        match statuses[cookies.len() - 1] {
+            200 => mock_response(200, &[], "{}"),
+            status => mock_response(status, &[], ""),
+        }
+    });
+    (url, cookies)
+}
+
+#[tokio::test]
+async fn refresh_on_expired_cookie() {
+    let (url, cookies) = serve(vec![401, 200]);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_clone = Arc::clone(&events);
+    let client = RustbloxClientBuilder::new()
+        .insert_cookie("_|WARNING:stale")
+        .unwrap()
+        .credential_provider(FixedCookie("_|WARNING:fresh"))
+        .on_auth_event(move |event| events_clone.lock().unwrap().push(event))
+        .build()
+        .unwrap();
+
+    let result = client
+        .request_json(Method::GET, url)
+        .authenticated(true)
+        .send()
+        .await;
+    assert!(result.is_ok());
+
+    assert_eq!(
+        *cookies.lock().unwrap(),
+        vec![
+            ".ROBLOSECURITY=_|WARNING:stale".to_string(),
+            ".ROBLOSECURITY=_|WARNING:fresh".to_string(),
+        ]
+    );
+    assert_eq!(
+        client.roblox_cookie(),
+        Some(".ROBLOSECURITY=_|WARNING:fresh".to_string())
+    );
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![AuthEvent::Expired, AuthEvent::Refreshed]
+    );
+}
+
+#[tokio::test]
+async fn refresh_only_once() {
+    let (url, cookies) = serve(vec![401, 401]);
+    let client = RustbloxClientBuilder::new()
+        .insert_cookie("_|WARNING:stale")
+        .unwrap()
+        .credential_provider(FixedCookie("_|WARNING:also-stale"))
+        .build()
+        .unwrap();
+
+    let result = client
+        .request_json(Method::GET, url)
+        .authenticated(true)
+        .send()
+        .await;
+    assert!(matches!(result, Err(RequestError::ExpiredCookie(_))));
+    assert_eq!(cookies.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn expired_without_provider() {
+    let (url, cookies) = serve(vec![401]);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_clone = Arc::clone(&events);
+    let client = RustbloxClientBuilder::new()
+        .insert_cookie("_|WARNING:stale")
+        .unwrap()
+        .on_auth_event(move |event| events_clone.lock().unwrap().push(event))
+        .build()
+        .unwrap();
+
+    let result = client
+        .request_json(Method::GET, url)
+        .authenticated(true)
+        .send()
+        .await;
+    assert!(matches!(result, Err(RequestError::ExpiredCookie(_))));
+    assert_eq!(cookies.lock().unwrap().len(), 1);
+    assert_eq!(*events.lock().unwrap(), vec![AuthEvent::Expired]);
+}
+
+#[tokio::test]
+async fn cookie_file() {
+    let path = std::env::temp_dir().join(format!("rustblox-cookie-{}", std::process::id()));
+    std::fs::write(&path, "_|WARNING:from-file\n").unwrap();
+
+    let cookie = CookieFile::new(&path).cookie().await;
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(cookie.unwrap(), Some("_|WARNING:from-file".to_string()));
+
+    let missing = CookieFile::new(&path).cookie().await;
+    assert!(matches!(
+        missing,
+        Err(RequestError::ReauthenticationFailed(_))
+    ));
+}
+
+#[tokio::test]
+async fn refreshed_cookie_gets_a_csrf_token() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let requests_clone = Arc::clone(&requests);
+    let url = mock_server(move |request| {
+        let mut requests = requests_clone.lock().unwrap();
+        requests.push(request.clone());
+        match requests.len() {
+            1 => mock_response(401, &[], ""),
+            2 => mock_response(
+                403,
+                &[("x-csrf-token", "fresh-token")],
+                r#"{"errors":[{"code":0,"message":"Token Validation Failed"}]}"#,
+            ),
+            _ => mock_response(200, &[], "{}"),
+        }
+    });
+    let client = RustbloxClientBuilder::new()
+        .insert_cookie("_|WARNING:stale")
+        .unwrap()
+        .credential_provider(FixedCookie("_|WARNING:fresh"))
+        .build()
+        .unwrap();
+
+    let result = client
+        .request_json(Method::POST, url)
+        .authenticated(true)
+        .send()
+        .await;
+    assert!(result.is_ok(), "{result:?}");
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 3);
+    assert!(requests.iter().all(|request| request.method == "POST"));
+    assert_eq!(
+        requests[1].header("cookie"),
+        Some(".ROBLOSECURITY=_|WARNING:fresh")
+    );
+    assert_eq!(requests[1].header("x-csrf-token"), None);
+    assert_eq!(requests[2].header("x-csrf-token"), Some("fresh-token"));
+}
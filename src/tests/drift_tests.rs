@@ -0,0 +1,74 @@
+use super::{mock_response, mock_server};
+use crate::client::builder::RustbloxClientBuilder;
+use crate::client::Method;
+use crate::drift::SchemaDrift;
+use crate::endpoint::Endpoint;
+use crate::structs::group::GroupRolesList;
+use std::sync::{Arc, Mutex};
+
+// Only the endpoint's URL matters here, so it can point at the mock server
+struct GetRoles {
+    url: String,
+}
+
+impl Endpoint for GetRoles {
+    type Body = ();
+    type Response = GroupRolesList;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn url(&self) -> String {
+        self.url.clone()
+    }
+}
+
+async fn drift_in(body: &'static str) -> Vec<SchemaDrift> {
+    let url = mock_server(move |_| mock_response(200, &[], body));
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let reports_clone = Arc::clone(&reports);
+    let client = RustbloxClientBuilder::new()
+        .on_schema_drift(move |drift| reports_clone.lock().unwrap().push(drift.clone()))
+        .build()
+        .unwrap();
+
+    assert!(client.execute(GetRoles { url }).await.is_ok());
+    let reports = reports.lock().unwrap().clone();
+    reports
+}
+
+#[tokio::test]
+async fn matching_responses_are_quiet() {
+    let drift = drift_in(
+        r#"{"groupId":1,"roles":[{"id":1,"name":"Guest","description":null,"rank":0,"memberCount":0}]}"#,
+    )
+    .await;
+
+    assert!(drift.is_empty());
+}
+
+#[tokio::test]
+async fn unknown_and_missing_fields_are_reported() {
+    let drift = drift_in(
+        r#"{"groupId":1,"roles":[{"id":1,"name":"Guest","rank":0,"memberCount":0,"color":"red"}]}"#,
+    )
+    .await;
+
+    assert_eq!(drift.len(), 1);
+    assert!(drift[0].target.ends_with("GroupRolesList"));
+    assert_eq!(drift[0].unknown_fields, vec!["roles[0].color".to_string()]);
+    assert_eq!(
+        drift[0].missing_fields,
+        vec!["roles[0].description".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn detection_is_off_by_default() {
+    let url = mock_server(|_| mock_response(200, &[], r#"{"groupId":1,"roles":[],"extra":true}"#));
+    let client = RustbloxClientBuilder::new().build().unwrap();
+
+    // Nothing to observe besides the request still working
+    assert!(client.execute(GetRoles { url }).await.is_ok());
+}
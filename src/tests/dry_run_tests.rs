@@ -0,0 +1,191 @@
+use super::{mock_response, mock_server};
+use crate::client::builder::RustbloxClientBuilder;
+use crate::client::{AuthMethod, Method, RequestComponents};
+use crate::endpoint::{
+    ChangeUsername, Endpoint, GetUserJoinRequest, GetUserPresences, GetUsersFromIds, KickUser,
+    SetUserRoleInGroup, ValidateUsername,
+};
+use crate::error::RequestError;
+use crate::structs::auth::UsernameContext;
+use std::sync::{Arc, Mutex};
+
+fn dry_run_client() -> crate::client::RustbloxClient {
+    RustbloxClientBuilder::new()
+        .insert_cookie("_|WARNING:fake-cookie")
+        .unwrap()
+        .dry_run(true)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn mutating_calls_are_skipped() {
+    let client = dry_run_client();
+    assert!(client.is_dry_run());
+
+    // None of these reach Roblox, so the fake cookie doesn't matter
+    assert!(client.kick_user(1, 1).await.is_ok());
+    assert!(client.accept_user_join_request(1, 1).await.is_ok());
+    assert!(client.batch_deny_requests(1).await.is_ok());
+    assert!(client
+        .change_display_name(1, "Dry Run".to_string())
+        .await
+        .is_ok());
+    assert_eq!(
+        client
+            .set_authenticated_user_description("Dry Run")
+            .await
+            .unwrap(),
+        "Dry Run"
+    );
+}
+
+#[tokio::test]
+async fn read_only_calls_go_through() {
+    let methods = Arc::new(Mutex::new(Vec::new()));
+    let methods_clone = Arc::clone(&methods);
+    let url = mock_server(move |request| {
+        methods_clone.lock().unwrap().push(request.method.clone());
+        mock_response(200, &[], "{}")
+    });
+    let client = dry_run_client();
+
+    let read = client
+        .request_json(Method::GET, url.as_str())
+        .authenticated(true)
+        .send()
+        .await;
+    assert!(read.is_ok());
+    let write = client
+        .request_json(Method::POST, url.as_str())
+        .authenticated(true)
+        .send()
+        .await;
+    assert_eq!(write.unwrap(), serde_json::Value::Null);
+    let marked_read_only = client
+        .request_json(Method::POST, url.as_str())
+        .mutating(false)
+        .send()
+        .await;
+    assert!(marked_read_only.is_ok());
+
+    assert_eq!(*methods.lock().unwrap(), vec!["GET", "POST"]);
+}
+
+#[tokio::test]
+async fn dry_run_still_needs_credentials() {
+    let client = RustbloxClientBuilder::new().dry_run(true).build().unwrap();
+    let result = client.kick_user(1, 1).await;
+    assert!(matches!(result, Err(RequestError::NotAuthenticated)));
+}
+
+#[test]
+fn mutating_endpoints() {
+    assert!(KickUser {
+        group_id: 1,
+        user_id: 1
+    }
+    .mutating());
+    assert!(SetUserRoleInGroup {
+        group_id: 1,
+        user_id: 1,
+        role_id: 1
+    }
+    .mutating());
+    assert!(!GetUserJoinRequest {
+        group_id: 1,
+        user_id: 1
+    }
+    .mutating());
+    assert!(ChangeUsername {
+        username: "NewName".to_string(),
+        password: "hunter2".to_string(),
+    }
+    .mutating());
+}
+
+#[test]
+fn read_only_posts_opt_out() {
+    assert!(!GetUsersFromIds {
+        user_ids: vec![1],
+        exclude_banned: false
+    }
+    .mutating());
+    assert!(!GetUserPresences {
+        user_ids: vec![1],
+        authenticated: false
+    }
+    .mutating());
+    assert!(!ValidateUsername {
+        username: "NewName".to_string(),
+        context: UsernameContext::UsernameChange
+    }
+    .mutating());
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+struct Created {
+    id: usize,
+}
+
+struct CreateThing<T>(std::marker::PhantomData<T>);
+
+impl<T> Endpoint for CreateThing<T>
+where
+    T: serde::de::DeserializeOwned + serde::Serialize,
+{
+    type Body = ();
+    type Response = T;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn url(&self) -> String {
+        "http://127.0.0.1:9/".to_string()
+    }
+}
+
+#[tokio::test]
+async fn custom_endpoint_responses() {
+    let client = dry_run_client();
+
+    let optional = client
+        .execute(CreateThing::<Option<Created>>(std::marker::PhantomData))
+        .await;
+    assert_eq!(optional.unwrap(), None);
+
+    // Nothing to make up a `Created` from
+    let required = client
+        .execute(CreateThing::<Created>(std::marker::PhantomData))
+        .await;
+    assert!(matches!(required, Err(RequestError::RequestError(..))));
+}
+
+#[test]
+fn summaries_leave_the_body_out() {
+    let components = RequestComponents {
+        auth: AuthMethod::Cookie,
+        method: Method::POST,
+        url: "https://auth.roblox.com/v2/username".to_string(),
+        headers: None,
+        body: Some(r#"{"username":"builderman","password":"hunter2"}"#.to_string()),
+        mutating: true,
+        drift_check: None,
+        action: Some(r#"ChangeUsername {"new_username":"builderman"}"#.to_string()),
+        permission_error_codes: Vec::new(),
+    };
+    assert_eq!(
+        components.dry_run_summary(),
+        r#"POST https://auth.roblox.com/v2/username (ChangeUsername {"new_username":"builderman"})"#
+    );
+
+    let raw = RequestComponents {
+        action: None,
+        ..components
+    };
+    assert_eq!(
+        raw.dry_run_summary(),
+        "POST https://auth.roblox.com/v2/username"
+    );
+}
@@ -0,0 +1,82 @@
+use crate::client::builder::RustbloxClientBuilder;
+use crate::client::AuthMethod;
+use crate::endpoint::{
+    ChangeDisplayName, Endpoint, GetGroupMembers, GetUserInfo, GetUsersFromIds, KickUser,
+    SearchUser, SetUserRoleInGroup,
+};
+use crate::structs::SortOrder;
+use reqwest::Method;
+
+#[test]
+fn endpoint_descriptions() {
+    let kick = KickUser {
+        group_id: 1,
+        user_id: 2,
+    };
+    assert_eq!(kick.method(), Method::DELETE);
+    assert_eq!(kick.url(), "https://groups.roblox.com/v1/groups/1/users/2");
+    assert_eq!(kick.auth(), AuthMethod::Cookie);
+    assert!(kick.body().is_none());
+
+    let members = GetGroupMembers {
+        group_id: 1,
+        limit: None,
+        cursor: Some("abc".to_string()),
+        sort_order: Some(SortOrder::Descending),
+    };
+    assert_eq!(members.auth(), AuthMethod::None);
+    assert_eq!(
+        members.query(),
+        vec![
+            ("limit", "10".to_string()),
+            ("cursor", "abc".to_string()),
+            ("sortOrder", "Desc".to_string()),
+        ]
+    );
+
+    let search = SearchUser {
+        keyword: "builderman".to_string(),
+        limit: Some(25),
+        cursor: None,
+    };
+    assert_eq!(search.query()[0], ("keyword", "builderman".to_string()));
+}
+
+#[test]
+fn endpoint_bodies() {
+    let rank = SetUserRoleInGroup {
+        group_id: 1,
+        user_id: 2,
+        role_id: 3,
+    };
+    assert_eq!(
+        serde_json::to_value(rank.body()).unwrap(),
+        serde_json::json!({ "roleId": 3 })
+    );
+
+    let display_name = ChangeDisplayName {
+        user_id: 1,
+        new_name: "Builder".to_string(),
+    };
+    assert_eq!(
+        serde_json::to_value(display_name.body()).unwrap(),
+        serde_json::json!({ "newDisplayName": "Builder" })
+    );
+
+    let users = GetUsersFromIds {
+        user_ids: vec![1, 2],
+        exclude_banned: true,
+    };
+    assert_eq!(
+        serde_json::to_value(users.body()).unwrap(),
+        serde_json::json!({ "userIds": [1, 2], "excludeBannedUsers": true })
+    );
+}
+
+#[tokio::test]
+async fn execute_endpoint() {
+    let client = RustbloxClientBuilder::new().build().unwrap();
+    let result = client.execute(GetUserInfo { user_id: 1 }).await;
+    println!("{:#?}", result);
+    assert!(result.is_ok());
+}
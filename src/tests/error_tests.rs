@@ -0,0 +1,165 @@
+use super::{mock_response, mock_server};
+use crate::client::builder::RustbloxClientBuilder;
+use crate::client::Method;
+use crate::error::{DeserializeError, RequestError, UsernameChangeError};
+use crate::routes::authenticated::auth::username_change_error;
+use crate::structs::group::GroupRole;
+use crate::structs::oauth::OAuthToken;
+use crate::structs::DataWrapper;
+
+async fn post_to(
+    status: u16,
+    headers: &'static [(&'static str, &'static str)],
+    body: &'static str,
+) -> RequestError {
+    let url = mock_server(move |_| mock_response(status, headers, body));
+    let client = RustbloxClientBuilder::new().build().unwrap();
+
+    client
+        .request_json(Method::POST, format!("{url}/v1/thing").as_str())
+        .permission_error_codes(&[4])
+        .send()
+        .await
+        .unwrap_err()
+}
+
+#[tokio::test]
+async fn missing_permission_is_distinct() {
+    let error = post_to(
+        403,
+        &[],
+        r#"{"errors":[{"code":4,"message":"You do not have permission to manage this member."}]}"#,
+    )
+    .await;
+
+    assert!(matches!(error, RequestError::InsufficientPermissions(_)));
+    let http = error.http_error().unwrap();
+    assert_eq!(http.method, Method::POST);
+    assert!(http.url.ends_with("/v1/thing"));
+    assert_eq!(http.code(), Some(4));
+}
+
+#[tokio::test]
+async fn other_403s_are_forbidden() {
+    let error = post_to(
+        403,
+        &[],
+        r#"{"errors":[{"code":1,"message":"The group is locked."}]}"#,
+    )
+    .await;
+
+    assert!(matches!(error, RequestError::Forbidden(_)));
+    assert_eq!(error.status(), Some(403));
+
+    // Only the code counts, not the wording
+    let error = post_to(
+        403,
+        &[],
+        r#"{"errors":[{"code":7,"message":"You do not have permission to do that."}]}"#,
+    )
+    .await;
+    assert!(matches!(error, RequestError::Forbidden(_)));
+}
+
+#[tokio::test]
+async fn unauthorized_without_cookie() {
+    let error = post_to(
+        401,
+        &[],
+        r#"{"errors":[{"code":0,"message":"Unauthorized"}]}"#,
+    )
+    .await;
+
+    assert!(matches!(error, RequestError::Unauthorized(_)));
+}
+
+#[tokio::test]
+async fn unparsable_bodies_are_kept() {
+    let error = post_to(
+        502,
+        &[("retry-after", "5"), ("set-cookie", "secret=1")],
+        "<html>Bad Gateway</html>",
+    )
+    .await;
+
+    assert!(matches!(error, RequestError::ServerError(_)));
+    let http = error.http_error().unwrap();
+    assert!(http.errors.errors.is_empty());
+    assert_eq!(http.body, "<html>Bad Gateway</html>");
+    assert_eq!(http.headers.get("retry-after").unwrap(), "5");
+    assert!(http.headers.get("set-cookie").is_none());
+    assert!(error.to_string().contains("Bad Gateway"));
+}
+
+#[tokio::test]
+async fn deserialize_errors_point_at_the_problem() {
+    let body = r#"{"data":[{"id":1,"name":"Guest","rank":0,"memberCount":5},{"id":2,"name":"Member","rank":1,"memberCount":"lots"}]}"#;
+    let url = mock_server(move |_| mock_response(200, &[], body));
+    let client = RustbloxClientBuilder::new().build().unwrap();
+
+    let error = client
+        .request::<DataWrapper<GroupRole>>(Method::GET, url.as_str())
+        .send()
+        .await
+        .unwrap_err();
+
+    let error = match error {
+        RequestError::Deserialize(error) => error,
+        other => panic!("expected a deserialize error, got {other:?}"),
+    };
+    assert_eq!(error.path, "data[1].memberCount");
+    assert!(error
+        .target
+        .ends_with("DataWrapper<rustblox::structs::group::GroupRole>"));
+    assert!(error.message.contains("expected usize"));
+    assert_eq!(error.body, body);
+}
+
+#[tokio::test]
+async fn deserialize_errors_truncate_the_body() {
+    let body = format!("[{}]", "1,".repeat(2000));
+    let error = DeserializeError::parse::<Vec<u8>>("https://example.com", &body).unwrap_err();
+
+    assert_eq!(error.body.len(), DeserializeError::BODY_LIMIT + 3);
+    assert!(error.body.ends_with("..."));
+}
+
+#[test]
+fn deserialize_errors_redact_tokens() {
+    let body = r#"{"access_token":"secret-access","refresh_token":"secret-refresh","nested":[{"id_token":"secret-id"}],"token_type":7}"#;
+    let error = DeserializeError::parse::<OAuthToken>("https://example.com", body).unwrap_err();
+    assert!(!error.body.contains("secret"));
+    assert_eq!(error.body.matches("[REDACTED]").count(), 3);
+    assert!(error.body.contains(r#""token_type":7"#));
+
+    // Not JSON, so nothing can be picked out of it
+    let body = "access_token=secret-access&expires_in=";
+    let error = DeserializeError::parse::<OAuthToken>("https://example.com", body).unwrap_err();
+    assert!(!error.body.contains("secret"));
+}
+
+#[tokio::test]
+async fn wrong_passwords_are_told_apart_by_code() {
+    let error = post_to(
+        403,
+        &[],
+        r#"{"errors":[{"code":3,"message":"Your password is incorrect."}]}"#,
+    )
+    .await;
+    assert!(matches!(
+        username_change_error(error),
+        RequestError::UsernameChange(UsernameChangeError::IncorrectPassword)
+    ));
+
+    // Mentioning a password isn't enough
+    let error = post_to(
+        403,
+        &[],
+        r#"{"errors":[{"code":5,"message":"This account's password has to be reset first."}]}"#,
+    )
+    .await;
+    assert!(matches!(
+        username_change_error(error),
+        RequestError::Forbidden(_)
+    ));
+}
@@ -1,18 +1,44 @@
+mod account_session_tests;
+mod audit_tests;
 mod auto_reauth;
+mod challenge_tests;
 mod client_init_tests;
+mod cloud_tests;
+mod credentials_tests;
+mod drift_tests;
+mod dry_run_tests;
+mod endpoint_tests;
+mod error_tests;
 mod group_auth_tests;
 mod group_unauth_tests;
+mod oauth_tests;
+mod presence_tests;
+mod raw_request_tests;
+mod serialization_tests;
+mod twostepverification_tests;
+mod url_tests;
 mod user_auth_tests;
 mod user_unauth_tests;
+mod validation_tests;
+mod verification_tests;
 
 use std::fs::{canonicalize, File};
-use std::io::Read;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
 use std::path::Path;
 
 #[inline]
 fn get_cookie() -> String {
-    let path = canonicalize(Path::new("resources/private/cookie.txt"))
-        .expect("Could not canonicalize file.");
+    read_private_file("resources/private/cookie.txt")
+}
+
+#[inline]
+fn get_api_key() -> String {
+    read_private_file("resources/private/api_key.txt")
+}
+
+fn read_private_file(path: &str) -> String {
+    let path = canonicalize(Path::new(path)).expect("Could not canonicalize file.");
 
     let mut handle = File::open(path).expect("Could not open file.");
 
@@ -33,3 +59,92 @@ fn get_cookie() -> String {
 
     buf.to_string()
 }
+
+/// A request received by a [`mock_server`].
+#[derive(Debug, Clone)]
+struct MockRequest {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl MockRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Starts an HTTP server on a random local port that answers every request with
+/// whatever `handler` returns (see [`mock_response`]). Returns the server's base URL.
+/// The server lives until the test process exits.
+fn mock_server<F>(handler: F) -> String
+where
+    F: Fn(&MockRequest) -> String + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Could not bind the mock server.");
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => continue,
+            };
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let path = parts.next().unwrap_or_default().to_string();
+
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    headers.push((name.trim().to_string(), value.trim().to_string()));
+                }
+            }
+
+            let mut request = MockRequest {
+                method,
+                path,
+                headers,
+                body: String::new(),
+            };
+            let length = request
+                .header("content-length")
+                .and_then(|length| length.parse::<usize>().ok())
+                .unwrap_or_default();
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.body = String::from_utf8_lossy(&body).into_owned();
+
+            stream.write_all(handler(&request).as_bytes()).unwrap();
+        }
+    });
+
+    url
+}
+
+/// Builds a raw HTTP response for a [`mock_server`] handler.
+fn mock_response(status: u16, headers: &[(&str, &str)], body: &str) -> String {
+    let mut response = format!(
+        "HTTP/1.1 {status} Mock\r\nConnection: close\r\nContent-Length: {}\r\n",
+        body.len()
+    );
+    for (name, value) in headers {
+        response.push_str(&format!("{name}: {value}\r\n"));
+    }
+    response.push_str("\r\n");
+    response.push_str(body);
+    response
+}
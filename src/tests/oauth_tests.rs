@@ -0,0 +1,97 @@
+use crate::client::builder::RustbloxClientBuilder;
+use crate::client::AuthMethod;
+use crate::endpoint::{Endpoint, RefreshOAuthToken};
+use crate::error::RequestError;
+use crate::oauth::{OAuthApp, PkceVerifier};
+use crate::structs::oauth::OAuthToken;
+
+#[test]
+fn pkce_challenge() {
+    // Example from RFC 7636 appendix B
+    let verifier =
+        PkceVerifier::from_string("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk".to_string());
+    assert_eq!(
+        verifier.challenge(),
+        "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+    );
+}
+
+#[test]
+fn pkce_verifier_length() {
+    let verifier = PkceVerifier::new().unwrap();
+    // 32 bytes, base64url-encoded without padding
+    assert_eq!(verifier.as_str().len(), 43);
+    assert_ne!(verifier, PkceVerifier::new().unwrap());
+}
+
+#[test]
+fn authorization_url() {
+    let app = OAuthApp::new("1234", "https://example.com/callback");
+    let verifier = PkceVerifier::from_string("verifier".to_string());
+    let url = app.authorization_url_with(&["openid", "profile"], "a state", &verifier);
+    let url = reqwest::Url::parse(&url).unwrap();
+
+    assert_eq!(url.host_str(), Some("apis.roblox.com"));
+    assert_eq!(url.path(), "/oauth/v1/authorize");
+    let pairs = url.query_pairs().into_owned().collect::<Vec<_>>();
+    for (key, value) in [
+        ("client_id", "1234"),
+        ("redirect_uri", "https://example.com/callback"),
+        ("scope", "openid profile"),
+        ("response_type", "code"),
+        ("state", "a state"),
+        ("code_challenge", verifier.challenge().as_str()),
+        ("code_challenge_method", "S256"),
+    ] {
+        assert!(pairs.contains(&(key.to_string(), value.to_string())));
+    }
+}
+
+#[test]
+fn refresh_form_body() {
+    let app = OAuthApp::new("1234", "https://example.com/callback").client_secret("s3cr&t");
+    let endpoint = RefreshOAuthToken {
+        client_id: app.client_id.clone(),
+        client_secret: app.client_secret.clone(),
+        refresh_token: "token".to_string(),
+    };
+
+    let body = serde_urlencoded::to_string(endpoint.body().unwrap()).unwrap();
+    assert_eq!(
+        body,
+        "grant_type=refresh_token&client_id=1234&client_secret=s3cr%26t&refresh_token=token"
+    );
+    assert!(!format!("{:?}", endpoint).contains("s3cr&t"));
+}
+
+#[test]
+fn token_debug_redacted() {
+    let token = OAuthToken {
+        access_token: "access".to_string(),
+        refresh_token: "refresh".to_string(),
+        token_type: "Bearer".to_string(),
+        expires_in: 900,
+        scope: "openid".to_string(),
+        id_token: None,
+    };
+    let debug = format!("{:?}", token);
+    assert!(!debug.contains("access\""));
+    assert!(!debug.contains("refresh\""));
+}
+
+#[tokio::test]
+async fn oauth_session_required() {
+    let client = RustbloxClientBuilder::new().build().unwrap();
+    let result = client.get_oauth_user_info().await;
+    assert!(matches!(result, Err(RequestError::NotAuthenticated)));
+
+    let result = client
+        .request_json(
+            reqwest::Method::GET,
+            "https://apis.roblox.com/oauth/v1/userinfo",
+        )
+        .auth(AuthMethod::OAuth)
+        .send()
+        .await;
+    assert!(matches!(result, Err(RequestError::NotAuthenticated)));
+}
@@ -0,0 +1,158 @@
+use crate::client::builder::RustbloxClientBuilder;
+use crate::endpoint::GetUserPresences;
+use crate::presence::{PresenceEvent, PresenceWatcher};
+use crate::structs::presence::{PresenceType, UserPresence, UserPresences};
+use std::time::Duration;
+
+fn presence(user_id: usize, presence_type: PresenceType, place_id: Option<u64>) -> UserPresence {
+    UserPresence {
+        user_id,
+        user_presence_type: presence_type,
+        last_location: String::new(),
+        place_id,
+        root_place_id: place_id,
+        universe_id: place_id.map(|id| id + 1),
+        job_id: place_id.map(|_| "a1b2c3".to_string()),
+        last_online: Some("2024-01-01T00:00:00Z".to_string()),
+    }
+}
+
+#[test]
+fn presences_parse() {
+    let presences = serde_json::from_value::<UserPresences>(serde_json::json!({
+        "userPresences": [
+            {
+                "userPresenceType": 2,
+                "lastLocation": "Natural Disaster Survival",
+                "placeId": 189707,
+                "rootPlaceId": 189707,
+                "gameId": "a1b2c3",
+                "universeId": 65241,
+                "userId": 156,
+                "lastOnline": "2024-01-01T00:00:00Z"
+            },
+            {
+                "userPresenceType": 0,
+                "lastLocation": "Offline",
+                "placeId": null,
+                "rootPlaceId": null,
+                "gameId": null,
+                "universeId": null,
+                "userId": 261
+            }
+        ]
+    }))
+    .unwrap();
+
+    let in_game = &presences.user_presences[0];
+    assert_eq!(in_game.user_presence_type, PresenceType::InGame);
+    assert_eq!(in_game.job_id.as_deref(), Some("a1b2c3"));
+    assert_eq!(in_game.universe_id, Some(65241));
+    let offline = &presences.user_presences[1];
+    assert_eq!(offline.user_presence_type, PresenceType::Offline);
+    assert!(offline.last_online.is_none());
+
+    let serialized = serde_json::to_value(in_game).unwrap();
+    assert_eq!(serialized["userPresenceType"], 2);
+    assert_eq!(serialized["gameId"], "a1b2c3");
+    assert!(serde_json::from_value::<PresenceType>(serde_json::json!(7)).is_err());
+}
+
+#[test]
+fn presence_requests() {
+    let request = GetUserPresences {
+        user_ids: vec![156, 261],
+        authenticated: true,
+    };
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({ "userIds": [156, 261] })
+    );
+}
+
+#[test]
+fn activity_ignores_last_online() {
+    let before = presence(156, PresenceType::InGame, Some(1));
+    let mut later = before.clone();
+    later.last_online = Some("2024-01-01T00:05:00Z".to_string());
+    assert!(before.same_activity(&later));
+
+    assert!(!before.same_activity(&presence(156, PresenceType::InGame, Some(2))));
+    assert!(!before.same_activity(&presence(156, PresenceType::InStudio, Some(1))));
+}
+
+#[test]
+fn watcher_reports_changes() {
+    let client = RustbloxClientBuilder::new().build().unwrap();
+    let (mut watcher, mut events) =
+        PresenceWatcher::new(client, [156, 261, 156], Duration::from_secs(30));
+    assert_eq!(watcher.watched(), &[156, 261]);
+
+    // The first poll reports everyone
+    let sent = watcher.update(vec![
+        presence(156, PresenceType::Offline, None),
+        presence(261, PresenceType::Website, None),
+        presence(1, PresenceType::Website, None),
+    ]);
+    assert_eq!(sent, 2);
+    for _ in 0..2 {
+        assert!(matches!(
+            events.try_recv(),
+            Ok(PresenceEvent::Changed { previous: None, .. })
+        ));
+    }
+    assert!(watcher.presence(1).is_none());
+
+    // Only 156 joining a game is new
+    let sent = watcher.update(vec![
+        presence(156, PresenceType::InGame, Some(1)),
+        presence(261, PresenceType::Website, None),
+    ]);
+    assert_eq!(sent, 1);
+    match events.try_recv() {
+        Ok(PresenceEvent::Changed {
+            previous: Some(previous),
+            current,
+        }) => {
+            assert_eq!(previous.user_presence_type, PresenceType::Offline);
+            assert_eq!(current.place_id, Some(1));
+        }
+        other => panic!("Expected a change, got {other:?}"),
+    }
+    assert!(events.try_recv().is_err());
+
+    watcher.unwatch(156);
+    assert_eq!(watcher.watched(), &[261]);
+    assert!(watcher.presence(156).is_none());
+    assert_eq!(
+        watcher.update(vec![presence(156, PresenceType::Offline, None)]),
+        0
+    );
+
+    drop(events);
+    assert_eq!(
+        watcher.update(vec![presence(261, PresenceType::Offline, None)]),
+        0
+    );
+}
+
+#[tokio::test]
+async fn get_user_presences() {
+    let client = RustbloxClientBuilder::new().build().unwrap();
+    let ids: Vec<usize> = (1..=60).collect();
+    let request = client.get_user_presences(&ids).await;
+    println!("{:#?}", request);
+    assert_eq!(request.unwrap().len(), 60);
+}
+
+#[tokio::test]
+async fn get_user_presences_with_expired_cookie() {
+    let client = RustbloxClientBuilder::new()
+        .insert_cookie("_|WARNING:fake-cookie")
+        .unwrap()
+        .build()
+        .unwrap();
+    let request = client.get_user_presences(&[156]).await;
+    println!("{:#?}", request);
+    assert_eq!(request.unwrap().len(), 1);
+}
@@ -0,0 +1,39 @@
+use crate::client::builder::RustbloxClientBuilder;
+use crate::client::Method;
+use crate::error::RequestError;
+
+#[tokio::test]
+async fn raw_request_needs_cookie() {
+    let client = RustbloxClientBuilder::new().build().unwrap();
+    let result = client
+        .request_json(
+            Method::GET,
+            "https://users.roblox.com/v1/users/authenticated",
+        )
+        .authenticated(true)
+        .send()
+        .await;
+    assert!(matches!(result, Err(RequestError::NotAuthenticated)));
+}
+
+#[tokio::test]
+async fn raw_request_rejects_bad_url() {
+    let client = RustbloxClientBuilder::new().build().unwrap();
+    let result = client
+        .request_json(Method::GET, "not a url")
+        .query("keyword", "a&b")
+        .send()
+        .await;
+    assert!(matches!(result, Err(RequestError::RequestError(..))));
+}
+
+#[tokio::test]
+async fn raw_request_get_user() {
+    let client = RustbloxClientBuilder::new().build().unwrap();
+    let result = client
+        .request_json(Method::GET, "https://users.roblox.com/v1/users/1")
+        .send()
+        .await;
+    println!("{:#?}", result);
+    assert!(result.is_ok());
+}
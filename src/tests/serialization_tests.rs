@@ -0,0 +1,275 @@
+use crate::endpoint::{
+    ChangeUsername, SetAuthenticatedUserBirthdate, SetAuthenticatedUserGender, ValidateUsername,
+};
+use crate::structs::auth::{
+    UsernameChangeQuote, UsernameContext, UsernameStatus, UsernameValidation, USERNAME_CHANGE_COST,
+};
+use crate::structs::group::{GroupInfo, GroupMemberInfo, GroupMemberUser, UserGroup};
+use crate::structs::user::{
+    AuthenticatedUserGender, Birthdate, Gender, MinimalUserInfo, PreviousUsername, UserInfo,
+    UserRef, UserSearchResult, UsernameLookupResult,
+};
+use crate::structs::{DataWrapper, Page};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt::Debug;
+
+fn round_trip<T>(json: serde_json::Value) -> T
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let parsed = serde_json::from_value::<T>(json).expect("Couldn't parse the original JSON");
+    let serialized = serde_json::to_string(&parsed).expect("Couldn't serialize");
+    let reparsed = serde_json::from_str::<T>(&serialized).expect("Couldn't parse serialized JSON");
+    assert_eq!(parsed, reparsed);
+    reparsed
+}
+
+#[test]
+fn user_info_round_trip() {
+    let info = round_trip::<UserInfo>(serde_json::json!({
+        "description": "Hello!",
+        "created": "2006-02-27T21:06:40.3Z",
+        "isBanned": false,
+        "externalAppDisplayName": null,
+        "hasVerifiedBadge": true,
+        "id": 156,
+        "name": "builderman",
+        "displayName": "builderman"
+    }));
+
+    let serialized = serde_json::to_value(&info).unwrap();
+    assert_eq!(serialized["hasVerifiedBadge"], true);
+    assert_eq!(serialized["displayName"], "builderman");
+}
+
+#[test]
+fn group_member_user_round_trip() {
+    // Groups endpoints use userId/username instead of id/name
+    let page = round_trip::<Page<GroupMemberUser>>(serde_json::json!({
+        "previousPageCursor": null,
+        "nextPageCursor": "abc",
+        "data": [{
+            "hasVerifiedBadge": false,
+            "userId": 1,
+            "username": "Roblox",
+            "displayName": "Roblox"
+        }]
+    }));
+    assert_eq!(page.data[0].user_id, 1);
+    assert_eq!(page.next_page_cursor.as_deref(), Some("abc"));
+
+    let serialized = serde_json::to_value(&page.data[0]).unwrap();
+    assert_eq!(serialized["userId"], 1);
+    assert_eq!(serialized["username"], "Roblox");
+}
+
+#[test]
+fn user_lookup_round_trip() {
+    let found = round_trip::<DataWrapper<UsernameLookupResult>>(serde_json::json!({
+        "data": [{
+            "requestedUsername": "builderman",
+            "hasVerifiedBadge": true,
+            "id": 156,
+            "name": "builderman",
+            "displayName": "builderman"
+        }]
+    }));
+    assert_eq!(found.data[0].requested_username, "builderman");
+
+    let searched = round_trip::<Page<UserSearchResult>>(serde_json::json!({
+        "previousPageCursor": null,
+        "nextPageCursor": null,
+        "data": [{
+            "previousUsernames": ["builder"],
+            "hasVerifiedBadge": true,
+            "id": 156,
+            "name": "builderman",
+            "displayName": "builderman"
+        }]
+    }));
+    assert_eq!(
+        searched.data[0].previous_usernames,
+        vec!["builder".to_string()]
+    );
+}
+
+#[test]
+fn previous_usernames_round_trip() {
+    let page = round_trip::<Page<PreviousUsername>>(serde_json::json!({
+        "previousPageCursor": null,
+        "nextPageCursor": null,
+        "data": [{ "name": "builder" }]
+    }));
+    assert_eq!(page.data[0].name, "builder");
+}
+
+#[test]
+fn users_convert_to_minimal_info() {
+    let member = GroupMemberUser {
+        has_verified_badge: false,
+        user_id: 1,
+        username: "Roblox".to_string(),
+        display_name: "Roblox".to_string(),
+    };
+    let searched = UserSearchResult {
+        previous_usernames: Vec::new(),
+        has_verified_badge: false,
+        id: 1,
+        name: "Roblox".to_string(),
+        display_name: "Roblox".to_string(),
+    };
+
+    assert_eq!(member.id(), searched.id());
+    assert_eq!(member.name(), searched.name());
+    assert_eq!(
+        MinimalUserInfo::from(member),
+        MinimalUserInfo::from(searched)
+    );
+}
+
+#[test]
+fn group_round_trip() {
+    let members = round_trip::<Page<GroupMemberInfo>>(serde_json::json!({
+        "previousPageCursor": null,
+        "nextPageCursor": null,
+        "data": [{
+            "user": {
+                "hasVerifiedBadge": false,
+                "userId": 1,
+                "username": "Roblox",
+                "displayName": "Roblox"
+            },
+            "role": {
+                "id": 2,
+                "name": "Owner",
+                "rank": 255
+            }
+        }]
+    }));
+
+    let groups = round_trip::<DataWrapper<UserGroup>>(serde_json::json!({
+        "data": [{
+            "group": {
+                "id": 7,
+                "name": "Roblox",
+                "description": "",
+                "owner": {
+                    "hasVerifiedBadge": true,
+                    "userId": 1,
+                    "username": "Roblox",
+                    "displayName": "Roblox"
+                },
+                "shout": null,
+                "memberCount": 100,
+                "isBuildersClubOnly": false,
+                "publicEntryAllowed": true,
+                "hasVerifiedBadge": true
+            },
+            "role": {
+                "id": 2,
+                "name": "Owner",
+                "rank": 255
+            },
+            "isPrimaryGroup": true
+        }]
+    }));
+
+    let infos: HashSet<GroupInfo> = groups.data.into_iter().map(|g| g.group).collect();
+    assert_eq!(infos.len(), 1);
+    assert_eq!(members.data[0].role.rank, 255);
+}
+
+#[test]
+fn account_information_round_trip() {
+    let gender = round_trip::<AuthenticatedUserGender>(serde_json::json!({ "gender": 3 }));
+    assert_eq!(gender.gender, Gender::Female);
+    assert!(
+        serde_json::from_value::<AuthenticatedUserGender>(serde_json::json!({ "gender": 9 }))
+            .is_err()
+    );
+
+    let birthdate = round_trip::<Birthdate>(serde_json::json!({
+        "birthMonth": 2,
+        "birthDay": 27,
+        "birthYear": 2006
+    }));
+    assert_eq!(birthdate.birth_year, 2006);
+}
+
+#[test]
+fn account_information_requests() {
+    let gender = serde_json::to_value(SetAuthenticatedUserGender {
+        gender: Gender::Male,
+    })
+    .unwrap();
+    assert_eq!(gender, serde_json::json!({ "gender": "Male" }));
+
+    let request = SetAuthenticatedUserBirthdate {
+        birthdate: Birthdate {
+            birth_month: 2,
+            birth_day: 27,
+            birth_year: 2006,
+        },
+        password: "hunter2".to_string(),
+    };
+    let body = serde_json::to_value(&request).unwrap();
+    assert_eq!(body["birthMonth"], 2);
+    assert_eq!(body["password"], "hunter2");
+    assert!(!format!("{request:?}").contains("hunter2"));
+}
+
+#[test]
+fn username_validation_statuses() {
+    let status = |code: i16| {
+        round_trip::<UsernameValidation>(serde_json::json!({
+            "code": code,
+            "message": "Some message"
+        }))
+        .status()
+    };
+    assert_eq!(status(0), UsernameStatus::Valid);
+    assert_eq!(status(1), UsernameStatus::Taken);
+    assert_eq!(status(2), UsernameStatus::Moderated);
+    assert_eq!(status(10), UsernameStatus::Moderated);
+    assert_eq!(
+        status(3),
+        UsernameStatus::Invalid("Some message".to_string())
+    );
+}
+
+#[test]
+fn username_change_requests() {
+    let validate = serde_json::to_value(ValidateUsername {
+        username: "NewName".to_string(),
+        context: UsernameContext::UsernameChange,
+    })
+    .unwrap();
+    assert_eq!(
+        validate,
+        serde_json::json!({ "username": "NewName", "context": "UsernameChange" })
+    );
+
+    let request = ChangeUsername {
+        username: "NewName".to_string(),
+        password: "hunter2".to_string(),
+    };
+    let body = serde_json::to_value(&request).unwrap();
+    assert_eq!(body["password"], "hunter2");
+    assert!(!format!("{request:?}").contains("hunter2"));
+}
+
+#[test]
+fn username_change_quotes() {
+    let quote = |status: UsernameStatus, balance: u64| UsernameChangeQuote {
+        current_username: "OldName".to_string(),
+        username: "NewName".to_string(),
+        status,
+        cost: USERNAME_CHANGE_COST,
+        balance,
+    };
+    assert!(quote(UsernameStatus::Valid, USERNAME_CHANGE_COST).can_change());
+    assert!(!quote(UsernameStatus::Valid, USERNAME_CHANGE_COST - 1).can_change());
+    assert!(!quote(UsernameStatus::Taken, USERNAME_CHANGE_COST * 2).can_change());
+}
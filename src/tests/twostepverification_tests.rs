@@ -0,0 +1,76 @@
+use crate::challenge::{Challenge, ChallengeSolver};
+use crate::client::builder::RustbloxClientBuilder;
+use crate::error::RequestError;
+use crate::twostepverification::{Totp, TwoStepVerificationSolver};
+use reqwest::header::{HeaderMap, HeaderValue};
+
+// Test vectors from RFC 6238 appendix B (SHA1)
+const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+#[test]
+fn rfc_6238_vectors() {
+    let totp = Totp::from_base32(RFC_SECRET).unwrap().digits(8).unwrap();
+    assert_eq!(totp.generate_at(59), "94287082");
+    assert_eq!(totp.generate_at(1111111109), "07081804");
+    assert_eq!(totp.generate_at(1111111111), "14050471");
+    assert_eq!(totp.generate_at(1234567890), "89005924");
+    assert_eq!(totp.generate_at(2000000000), "69279037");
+    assert_eq!(totp.generate_at(20000000000), "65353130");
+}
+
+#[test]
+fn six_digit_codes() {
+    let totp = Totp::new(b"12345678901234567890".to_vec());
+    assert_eq!(totp.generate_at(59), "287082");
+    assert_eq!(totp.generate().len(), 6);
+}
+
+#[test]
+fn invalid_settings() {
+    let totp = || Totp::new(b"12345678901234567890".to_vec());
+    assert!(totp().digits(0).is_err());
+    assert!(totp().digits(11).is_err());
+    assert!(totp().digits(u32::MAX).is_err());
+    assert!(totp().period(0).is_err());
+
+    let longest = totp().digits(10).unwrap().period(1).unwrap();
+    assert_eq!(longest.generate_at(u64::MAX).len(), 10);
+}
+
+#[test]
+fn base32_secret_formats() {
+    let spaced = Totp::from_base32("gezd gnbv gy3t qojq gezd gnbv gy3t qojq").unwrap();
+    let plain = Totp::from_base32(RFC_SECRET).unwrap();
+    assert_eq!(spaced.generate_at(59), plain.generate_at(59));
+
+    assert!(Totp::from_base32("not base32!").is_err());
+    assert!(Totp::from_base32("").is_err());
+    assert!(RustbloxClientBuilder::new().totp_secret("1").is_err());
+    assert!(RustbloxClientBuilder::new().totp_secret(RFC_SECRET).is_ok());
+}
+
+#[tokio::test]
+async fn unreadable_metadata() {
+    let mut headers = HeaderMap::new();
+    headers.insert("rblx-challenge-id", HeaderValue::from_static("abc-123"));
+    headers.insert(
+        "rblx-challenge-type",
+        HeaderValue::from_static("twostepverification"),
+    );
+    // {"userId":"not a number","challengeId":"def-456","actionType":"Generic"}
+    headers.insert(
+        "rblx-challenge-metadata",
+        HeaderValue::from_static(
+            "eyJ1c2VySWQiOiJub3QgYSBudW1iZXIiLCJjaGFsbGVuZ2VJZCI6ImRlZi00NTYiLCJhY3Rpb25UeXBlIjoiR2VuZXJpYyJ9",
+        ),
+    );
+    let challenge = Challenge::from_headers(&headers).unwrap();
+
+    // Nothing reaches Roblox, since the metadata is checked first
+    let client = RustbloxClientBuilder::new().build().unwrap();
+    let solver = TwoStepVerificationSolver::new(Totp::new(b"12345678901234567890".to_vec()));
+    match solver.solve(&client, &challenge).await {
+        Err(RequestError::ChallengeFailed(failed, _)) => assert_eq!(failed.id, "abc-123"),
+        other => panic!("Expected a failed challenge, got {other:?}"),
+    }
+}
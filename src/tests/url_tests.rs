@@ -0,0 +1,105 @@
+use super::{mock_response, mock_server};
+use crate::client::builder::RustbloxClientBuilder;
+use crate::client::Method;
+use crate::endpoint::{Endpoint, SearchUser, UrlBuilder, ValidateUserDisplayName};
+use std::sync::{Arc, Mutex};
+
+const HOSTILE: &[&str] = &[
+    "a&limit=100",
+    "a#fragment",
+    "a b+c",
+    "../../v2/secret",
+    "a?b=c",
+    "100%25",
+    "Ünïcødé 名前",
+];
+
+#[test]
+fn segments_stay_in_place() {
+    for value in HOSTILE {
+        let url = UrlBuilder::new("https://users.roblox.com/v1/")
+            .path("/users/")
+            .segment(value)
+            .path("display-names")
+            .build();
+        let parsed = reqwest::Url::parse(&url).unwrap();
+
+        let segments: Vec<&str> = parsed.path_segments().unwrap().collect();
+        assert_eq!(segments.len(), 4, "{value} became {url}");
+        assert_eq!(segments[0], "v1");
+        assert_eq!(segments[3], "display-names");
+        assert!(parsed.query().is_none() && parsed.fragment().is_none());
+
+        let decoded = percent_encoding::percent_decode_str(segments[2])
+            .decode_utf8()
+            .unwrap();
+        assert_eq!(decoded, *value);
+    }
+}
+
+#[test]
+fn numeric_segments_are_unchanged() {
+    let url = UrlBuilder::new("https://groups.roblox.com")
+        .path("v1/groups")
+        .segment(7)
+        .path("users")
+        .segment(1)
+        .build();
+
+    assert_eq!(url, "https://groups.roblox.com/v1/groups/7/users/1");
+}
+
+#[test]
+fn endpoints_leave_query_values_raw() {
+    // Query values are encoded once, when the request is sent
+    let search = SearchUser {
+        keyword: "a&b".to_string(),
+        limit: None,
+        cursor: None,
+    };
+    assert!(search.query().contains(&("keyword", "a&b".to_string())));
+
+    let validate = ValidateUserDisplayName {
+        user_id: 1,
+        display_name: "a#b".to_string(),
+    };
+    assert_eq!(validate.query(), vec![("displayName", "a#b".to_string())]);
+}
+
+#[tokio::test]
+async fn hostile_values_reach_the_server_intact() {
+    let paths = Arc::new(Mutex::new(Vec::new()));
+    let paths_clone = Arc::clone(&paths);
+    let url = mock_server(move |request| {
+        paths_clone.lock().unwrap().push(request.path.clone());
+        mock_response(200, &[], "{}")
+    });
+    let client = RustbloxClientBuilder::new().build().unwrap();
+
+    for value in HOSTILE {
+        let endpoint = UrlBuilder::new(&url).path("users").segment(value).build();
+        client
+            .request_json(Method::GET, endpoint)
+            .query("keyword", value)
+            .query("limit", 10)
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let paths = paths.lock().unwrap();
+    for (value, path) in HOSTILE.iter().zip(paths.iter()) {
+        let parsed = reqwest::Url::parse(&format!("http://localhost{path}")).unwrap();
+        let segments: Vec<&str> = parsed.path_segments().unwrap().collect();
+        assert_eq!(segments.len(), 2, "{value} became {path}");
+
+        let query: Vec<(String, String)> = parsed.query_pairs().into_owned().collect();
+        assert_eq!(
+            query,
+            vec![
+                ("keyword".to_string(), value.to_string()),
+                ("limit".to_string(), "10".to_string())
+            ]
+        );
+    }
+}
@@ -64,3 +64,43 @@ async fn validate_display_name() {
     println!("{:#?}", request);
     assert!(request.is_ok());
 }
+
+#[tokio::test]
+async fn get_user_description() {
+    let client = create_authed_client().await;
+    let request = client.get_user_description().await;
+    println!("{:#?}", request);
+    assert!(request.is_ok());
+}
+
+#[tokio::test]
+async fn get_authed_user_birthdate() {
+    let client = create_authed_client().await;
+    let request = client.get_authenticated_user_birthdate().await;
+    println!("{:#?}", request);
+    assert!(request.is_ok());
+}
+
+#[tokio::test]
+async fn get_authed_user_gender() {
+    let client = create_authed_client().await;
+    let request = client.get_authenticated_user_gender().await;
+    println!("{:#?}", request);
+    assert!(request.is_ok());
+}
+
+#[tokio::test]
+async fn validate_username_for_change() {
+    let client = create_authed_client().await;
+    let request = client.validate_username_for_change("TestingName").await;
+    println!("{:#?}", request);
+    assert!(request.is_ok());
+}
+
+#[tokio::test]
+async fn prepare_username_change() {
+    let client = create_authed_client().await;
+    let request = client.prepare_username_change("TestingName").await;
+    println!("{:#?}", request);
+    assert!(request.is_ok());
+}
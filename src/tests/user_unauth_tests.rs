@@ -67,6 +67,32 @@ async fn get_previous_usernames() {
     println!("{:#?}", info);
 }
 
+#[tokio::test]
+async fn get_username_history() {
+    let client = create_unauthed_client().await;
+
+    let history = client.get_username_history(68429027).await;
+    if let Err(why) = history {
+        panic!("Had error getting username history:\n{}", why);
+    }
+
+    let history = history.unwrap();
+    println!("{:#?}", history);
+}
+
+#[tokio::test]
+async fn find_previous_holders() {
+    let client = create_unauthed_client().await;
+
+    let holders = client.find_previous_holders("TheWildDeveloper").await;
+    if let Err(why) = holders {
+        panic!("Had error finding previous holders:\n{}", why);
+    }
+
+    let holders = holders.unwrap();
+    println!("{:#?}", holders);
+}
+
 #[tokio::test]
 async fn search_user() {
     let client = create_unauthed_client().await;
@@ -81,3 +107,11 @@ async fn search_user() {
     let info = user_info.unwrap();
     println!("{:#?}", info);
 }
+
+#[tokio::test]
+async fn is_username_available() {
+    let client = create_unauthed_client().await;
+    let request = client.is_username_available("Roblox").await;
+    println!("{:#?}", request);
+    assert!(!request.unwrap());
+}
@@ -0,0 +1,73 @@
+use crate::client::builder::RustbloxClientBuilder;
+use crate::error::{NameValidationError, RequestError};
+use crate::validation::{validate_display_name, validate_username};
+
+#[test]
+fn valid_usernames() {
+    assert!(validate_username("builderman").is_ok());
+    assert!(validate_username("The_Wild1").is_ok());
+    assert!(validate_username("abc").is_ok());
+    assert!(validate_username("a1234567890123456789").is_ok());
+}
+
+#[test]
+fn invalid_usernames() {
+    assert_eq!(
+        validate_username("ab"),
+        Err(NameValidationError::TooShort(2))
+    );
+    assert_eq!(
+        validate_username("a12345678901234567890"),
+        Err(NameValidationError::TooLong(21))
+    );
+    assert_eq!(
+        validate_username("hello there"),
+        Err(NameValidationError::InvalidCharacter(' '))
+    );
+    assert_eq!(
+        validate_username("héllo"),
+        Err(NameValidationError::InvalidCharacter('é'))
+    );
+    assert_eq!(
+        validate_username("_hello"),
+        Err(NameValidationError::LeadingOrTrailingUnderscore)
+    );
+    assert_eq!(
+        validate_username("he_ll_o"),
+        Err(NameValidationError::TooManyUnderscores)
+    );
+}
+
+#[test]
+fn display_names() {
+    assert!(validate_display_name("Testing Name").is_ok());
+    assert!(validate_display_name("Ünïcödé_Name").is_ok());
+    assert_eq!(
+        validate_display_name("Bad!"),
+        Err(NameValidationError::InvalidCharacter('!'))
+    );
+    assert_eq!(
+        validate_display_name(" Spaced"),
+        Err(NameValidationError::LeadingOrTrailingSpace)
+    );
+    // Length is counted in characters, not bytes
+    assert!(validate_display_name("ééééééééééééééééééé").is_ok());
+}
+
+#[tokio::test]
+async fn change_display_name_rejected_locally() {
+    // No cookie is set, so this would fail with NotAuthenticated if it got that far
+    let client = RustbloxClientBuilder::new().build().unwrap();
+    let result = client.change_display_name(1, "no".to_string()).await;
+    assert!(matches!(
+        result,
+        Err(RequestError::InvalidName(NameValidationError::TooShort(2)))
+    ));
+
+    let client = RustbloxClientBuilder::new()
+        .client_side_validation(false)
+        .build()
+        .unwrap();
+    let result = client.change_display_name(1, "no".to_string()).await;
+    assert!(matches!(result, Err(RequestError::NotAuthenticated)));
+}
@@ -0,0 +1,107 @@
+use crate::client::builder::RustbloxClientBuilder;
+use crate::error::ClientError;
+use crate::verification::{
+    description_contains, generate_phrase, VerificationResult, VerificationTarget, Verifier,
+    MIN_PHRASE_WORDS,
+};
+use std::collections::HashSet;
+use std::time::Duration;
+
+#[test]
+fn phrases_are_random_words() {
+    let phrase = generate_phrase(6).unwrap();
+    assert_eq!(phrase.split(' ').count(), 6);
+    assert!(phrase.chars().all(|c| c == ' ' || c.is_ascii_lowercase()));
+
+    let phrases: HashSet<String> = (0..20).map(|_| generate_phrase(6).unwrap()).collect();
+    assert_eq!(phrases.len(), 20);
+}
+
+#[test]
+fn short_phrases_are_refused() {
+    assert!(matches!(
+        generate_phrase(0),
+        Err(ClientError::PhraseTooShort(0))
+    ));
+    assert!(matches!(
+        generate_phrase(MIN_PHRASE_WORDS - 1),
+        Err(ClientError::PhraseTooShort(_))
+    ));
+
+    let verifier = Verifier::new(Duration::from_secs(60)).words(0);
+    let pending = verifier.start(156).unwrap();
+    assert_eq!(pending.phrase.split(' ').count(), MIN_PHRASE_WORDS);
+
+    // A blank description can't match anything
+    assert!(!description_contains("", &pending.phrase));
+    assert!(!description_contains("", ""));
+    assert!(!description_contains("   ", " "));
+}
+
+#[test]
+fn descriptions_are_compared_loosely() {
+    let phrase = "otter maple lantern";
+    assert!(description_contains(
+        "Hi!\nOtter  MAPLE\tlantern :)",
+        phrase
+    ));
+    assert!(!description_contains("otter maple", phrase));
+    assert!(!description_contains("otter maple lanterns", phrase));
+    assert!(!description_contains("otter #### lantern", phrase));
+}
+
+#[test]
+fn usernames_share_a_challenge() {
+    let verifier = Verifier::new(Duration::from_secs(60)).words(4);
+    let pending = verifier.start("Builderman").unwrap();
+    assert_eq!(pending.phrase.split(' ').count(), 4);
+
+    assert_eq!(verifier.pending("builderman"), Some(pending.clone()));
+    assert_eq!(
+        verifier.pending(VerificationTarget::Username("BUILDERMAN".to_string())),
+        Some(pending)
+    );
+    assert!(verifier.pending(156).is_none());
+
+    let replaced = verifier.start("builderman").unwrap();
+    assert_eq!(verifier.pending("Builderman"), Some(replaced));
+
+    verifier.cancel("builderman");
+    assert!(verifier.pending("Builderman").is_none());
+}
+
+#[tokio::test]
+async fn checks_without_a_live_challenge() {
+    // Neither of these reaches Roblox
+    let client = RustbloxClientBuilder::new().build().unwrap();
+    let verifier = Verifier::new(Duration::ZERO);
+
+    assert_eq!(
+        verifier.check(&client, 156).await.unwrap(),
+        VerificationResult::NotPending
+    );
+
+    verifier.start(156).unwrap();
+    assert!(verifier.pending(156).is_none());
+    assert_eq!(
+        verifier.check(&client, 156).await.unwrap(),
+        VerificationResult::Expired
+    );
+    // Expired challenges are discarded
+    assert_eq!(
+        verifier.check(&client, 156).await.unwrap(),
+        VerificationResult::NotPending
+    );
+}
+
+#[tokio::test]
+async fn live_mismatch() {
+    let client = RustbloxClientBuilder::new().build().unwrap();
+    let verifier = Verifier::new(Duration::from_secs(60));
+    verifier.start("builderman").unwrap();
+
+    let result = verifier.check(&client, "builderman").await;
+    println!("{:#?}", result);
+    assert_eq!(result.unwrap(), VerificationResult::Mismatch);
+    assert!(verifier.pending("builderman").is_some());
+}
@@ -0,0 +1,221 @@
+//! Two-step verification support for headless accounts.
+//!
+//! Accounts using an authenticator app for two-step verification are asked to
+//! verify sensitive actions (i.e. ranking users or sending payouts). If the account's
+//! authenticator secret is known, a [`Totp`] can generate the same codes the app would,
+//! and the [`TwoStepVerificationSolver`] uses it to complete these challenges without
+//! any human involvement.
+//!
+//! The easiest way to set this up is with
+//! [`RustbloxClientBuilder::totp_secret`](crate::client::builder::RustbloxClientBuilder::totp_secret).
+
+use crate::challenge::{Challenge, ChallengeSolution, ChallengeSolver, ChallengeType};
+use crate::client::RustbloxClient;
+use crate::error::{ClientError, RequestError};
+use crate::structs::twostepverification::TwoStepVerificationMetadata;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use std::fmt::{Debug, Formatter};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Decodes an RFC 4648 base32 string, ignoring case, spaces and padding.
+fn decode_base32(input: &str) -> Option<Vec<u8>> {
+    let mut output = Vec::with_capacity(input.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in input.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|a| *a as char == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    Some(output)
+}
+
+/// Generates time-based one-time passwords as described in RFC 6238, using HMAC-SHA1.
+/// The defaults (6 digits every 30 seconds) match what Roblox's authenticator codes use.
+#[derive(Clone)]
+pub struct Totp {
+    secret: Vec<u8>,
+    digits: u32,
+    period: u64,
+}
+
+impl Debug for Totp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Don't leak the secret into logs
+        f.debug_struct("Totp")
+            .field("digits", &self.digits)
+            .field("period", &self.period)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Totp {
+    /// Creates a generator from the raw bytes of a secret.
+    #[must_use]
+    pub fn new(secret: Vec<u8>) -> Self {
+        Self {
+            secret,
+            digits: 6,
+            period: 30,
+        }
+    }
+
+    /// Creates a generator from a base32-encoded secret. This is the form Roblox shows
+    /// when setting up an authenticator app (the text under the QR code).
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the secret is empty or isn't valid base32.
+    pub fn from_base32(secret: &str) -> Result<Self, ClientError> {
+        let decoded = decode_base32(secret).ok_or_else(|| {
+            ClientError::InvalidTotpSecret("The secret isn't valid base32".into())
+        })?;
+        if decoded.is_empty() {
+            return Err(ClientError::InvalidTotpSecret("The secret is empty".into()));
+        }
+        Ok(Self::new(decoded))
+    }
+
+    /// Sets the number of digits in each code. 6 by default.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if `digits` isn't between 1 and 10. Codes are cut
+    /// from a 31-bit number, so they never have more than 10 meaningful digits.
+    pub fn digits(mut self, digits: u32) -> Result<Self, ClientError> {
+        if !(1..=10).contains(&digits) {
+            return Err(ClientError::InvalidTotpSettings(format!(
+                "Codes must have between 1 and 10 digits, not {digits}"
+            )));
+        }
+        self.digits = digits;
+        Ok(self)
+    }
+
+    /// Sets how long each code is valid for, in seconds. 30 by default.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if `period` is 0.
+    pub fn period(mut self, period: u64) -> Result<Self, ClientError> {
+        if period == 0 {
+            return Err(ClientError::InvalidTotpSettings(
+                "Codes must be valid for at least a second".into(),
+            ));
+        }
+        self.period = period;
+        Ok(self)
+    }
+
+    /// Generates the code for a given Unix timestamp (in seconds).
+    #[must_use]
+    pub fn generate_at(&self, unix_time: u64) -> String {
+        let counter = unix_time / self.period;
+
+        let mut mac =
+            Hmac::<Sha1>::new_from_slice(&self.secret).expect("HMAC can take a key of any size");
+        mac.update(&counter.to_be_bytes());
+        let hash = mac.finalize().into_bytes();
+
+        // Dynamic truncation (RFC 4226 section 5.3)
+        let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([
+            hash[offset] & 0x7f,
+            hash[offset + 1],
+            hash[offset + 2],
+            hash[offset + 3],
+        ]);
+        let code = u64::from(binary) % 10_u64.pow(self.digits);
+
+        format!("{code:0width$}", width = self.digits as usize)
+    }
+
+    /// Generates the code for the current time.
+    #[must_use]
+    pub fn generate(&self) -> String {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        self.generate_at(now)
+    }
+}
+
+/// A [`ChallengeSolver`] that completes two-step verification challenges with
+/// codes from a [`Totp`]. Other challenge types are passed on.
+#[derive(Debug, Clone)]
+pub struct TwoStepVerificationSolver {
+    totp: Totp,
+}
+
+impl TwoStepVerificationSolver {
+    /// Creates a solver that answers with codes from `totp`.
+    #[must_use]
+    pub fn new(totp: Totp) -> Self {
+        Self { totp }
+    }
+}
+
+#[async_trait::async_trait]
+impl ChallengeSolver for TwoStepVerificationSolver {
+    async fn solve(
+        &self,
+        client: &RustbloxClient,
+        challenge: &Challenge,
+    ) -> Result<Option<ChallengeSolution>, RequestError> {
+        if challenge.challenge_type != ChallengeType::TwoStepVerification {
+            return Ok(None);
+        }
+
+        let metadata = challenge
+            .metadata_json()
+            .and_then(|json| serde_json::from_value::<TwoStepVerificationMetadata>(json).ok())
+            .ok_or_else(|| {
+                RequestError::ChallengeFailed(
+                    challenge.clone(),
+                    "Couldn't parse the two-step verification metadata".to_string(),
+                )
+            })?;
+        let user_id = metadata.user_id.parse::<usize>().map_err(|e| {
+            RequestError::ChallengeFailed(
+                challenge.clone(),
+                format!("Two-step verification metadata had an invalid user ID:\n{e}"),
+            )
+        })?;
+
+        debug!(
+            "Answering two-step verification challenge {}",
+            metadata.challenge_id
+        );
+        let token = client
+            .verify_authenticator_code(
+                user_id,
+                metadata.challenge_id.clone(),
+                metadata.action_type.clone(),
+                self.totp.generate(),
+            )
+            .await?;
+
+        Ok(Some(ChallengeSolution::new(
+            challenge,
+            serde_json::json!({
+                "verificationToken": token.verification_token,
+                "rememberDevice": false,
+                "challengeId": metadata.challenge_id,
+                "actionType": metadata.action_type,
+            }),
+        )))
+    }
+}
@@ -0,0 +1,78 @@
+//! Client-side checks for Roblox usernames and display names.
+//!
+//! These mirror the rules Roblox enforces server-side, so obviously bad names can be
+//! rejected without spending a (rate-limited) request. Roblox is still the final word:
+//! a name that passes here can still be taken or moderated.
+
+use crate::error::NameValidationError;
+
+/// The minimum length of a username or display name, in characters.
+pub const MIN_NAME_LENGTH: usize = 3;
+/// The maximum length of a username or display name, in characters.
+pub const MAX_NAME_LENGTH: usize = 20;
+
+fn check_length(name: &str) -> Result<(), NameValidationError> {
+    let length = name.chars().count();
+    if length < MIN_NAME_LENGTH {
+        return Err(NameValidationError::TooShort(length));
+    }
+    if length > MAX_NAME_LENGTH {
+        return Err(NameValidationError::TooLong(length));
+    }
+    Ok(())
+}
+
+/// Checks a username against Roblox's username rules:
+/// - Between 3 and 20 characters long
+/// - Only contains `a-z`, `A-Z`, `0-9` and underscores
+/// - Contains at most one underscore, which can't be at the start or end
+///
+/// # Errors
+///
+/// Returns the first rule that `username` breaks.
+pub fn validate_username(username: &str) -> Result<(), NameValidationError> {
+    check_length(username)?;
+
+    if let Some(c) = username
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || *c == '_'))
+    {
+        return Err(NameValidationError::InvalidCharacter(c));
+    }
+    if username.starts_with('_') || username.ends_with('_') {
+        return Err(NameValidationError::LeadingOrTrailingUnderscore);
+    }
+    if username.matches('_').count() > 1 {
+        return Err(NameValidationError::TooManyUnderscores);
+    }
+
+    Ok(())
+}
+
+/// Checks a display name against Roblox's display name rules:
+/// - Between 3 and 20 characters long
+/// - Only contains letters, numbers, underscores and spaces. Unlike usernames,
+///   letters from any language are allowed.
+/// - Doesn't start or end with an underscore or a space
+///
+/// # Errors
+///
+/// Returns the first rule that `display_name` breaks.
+pub fn validate_display_name(display_name: &str) -> Result<(), NameValidationError> {
+    check_length(display_name)?;
+
+    if let Some(c) = display_name
+        .chars()
+        .find(|c| !(c.is_alphanumeric() || *c == '_' || *c == ' '))
+    {
+        return Err(NameValidationError::InvalidCharacter(c));
+    }
+    if display_name.starts_with('_') || display_name.ends_with('_') {
+        return Err(NameValidationError::LeadingOrTrailingUnderscore);
+    }
+    if display_name.starts_with(' ') || display_name.ends_with(' ') {
+        return Err(NameValidationError::LeadingOrTrailingSpace);
+    }
+
+    Ok(())
+}
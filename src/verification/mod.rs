@@ -0,0 +1,298 @@
+//! Proving that someone owns a Roblox account.
+//!
+//! The usual way to do this (i.e. for a Discord bot) is to hand the person a phrase, have them
+//! put it in their profile description, then check that it's there. A [`Verifier`] keeps track
+//! of the phrases it has handed out and how long they're good for:
+//!
+//! ```no_run
+//! # async fn example(client: rustblox::client::RustbloxClient) {
+//! use rustblox::verification::{VerificationResult, Verifier};
+//! use std::time::Duration;
+//!
+//! let verifier = Verifier::new(Duration::from_secs(600));
+//! let pending = verifier.start("builderman").unwrap();
+//! println!("Put this in your profile description: {}", pending.phrase);
+//!
+//! // Once they say they've done it
+//! match verifier.check(&client, "builderman").await.unwrap() {
+//!     VerificationResult::Verified { user_id } => println!("{user_id} is theirs!"),
+//!     other => println!("Not verified: {other:?}"),
+//! }
+//! # }
+//! ```
+
+use crate::client::RustbloxClient;
+use crate::error::{ClientError, RequestError};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// 256 plain words, so one random byte picks one with no bias. They're all ordinary
+// English words so that Roblox's filter leaves them alone
+const WORDS: [&str; 256] = [
+    "acorn", "anchor", "apple", "apron", "arrow", "aspen", "autumn", "badge", "bagel", "bamboo",
+    "banjo", "barley", "basil", "basket", "beach", "beacon", "beaver", "berry", "birch", "biscuit",
+    "blanket", "blossom", "boat", "bonnet", "branch", "bread", "breeze", "brick", "bridge",
+    "brook", "bubble", "bucket", "butter", "button", "cabin", "cactus", "camel", "candle", "canoe",
+    "canvas", "canyon", "carrot", "castle", "cedar", "cello", "cherry", "chess", "cloud", "clover",
+    "coast", "cobalt", "cocoa", "comet", "compass", "cookie", "copper", "coral", "cotton",
+    "crayon", "cricket", "crystal", "cupcake", "cypress", "daisy", "dolphin", "donut", "dragon",
+    "drum", "dune", "eagle", "easel", "ember", "falcon", "feather", "fern", "fiddle", "field",
+    "finch", "flame", "flute", "forest", "fossil", "fountain", "fox", "galaxy", "garden", "garnet",
+    "ginger", "glacier", "globe", "goose", "grape", "gravel", "guitar", "hammock", "harbor",
+    "harp", "harvest", "hazel", "hedge", "heron", "hill", "honey", "horizon", "igloo", "island",
+    "ivory", "jacket", "jade", "jasmine", "jelly", "jungle", "juniper", "kayak", "kettle", "kite",
+    "kiwi", "koala", "ladder", "lagoon", "lantern", "lemon", "lichen", "lily", "lime", "llama",
+    "lobster", "lotus", "magnet", "mallow", "mango", "maple", "marble", "marigold", "meadow",
+    "melon", "meteor", "mint", "mitten", "moon", "mosaic", "moss", "mountain", "muffin", "nebula",
+    "nectar", "noodle", "nutmeg", "nutshell", "oak", "oatmeal", "ocean", "olive", "onion", "orbit",
+    "orchard", "otter", "owl", "paddle", "panda", "paper", "paprika", "parrot", "parsley", "peach",
+    "peanut", "pearl", "pebble", "pelican", "pepper", "piano", "pickle", "pillow", "pine",
+    "planet", "plum", "pocket", "pond", "poppy", "pretzel", "pumpkin", "puzzle", "quartz", "quill",
+    "quiver", "rabbit", "radish", "rainbow", "raven", "reef", "ribbon", "river", "robin", "rocket",
+    "rosemary", "saddle", "saffron", "sail", "salmon", "sandal", "satchel", "scarf", "seal",
+    "shell", "sierra", "silver", "sketch", "sled", "snow", "sparrow", "spoon", "spruce", "squash",
+    "squirrel", "star", "stone", "storm", "sugar", "summit", "sunset", "swan", "teapot", "thistle",
+    "thunder", "tiger", "timber", "toast", "tomato", "topaz", "torch", "tortoise", "tower",
+    "trail", "trumpet", "tulip", "tundra", "turnip", "turtle", "valley", "velvet", "violet",
+    "violin", "walnut", "walrus", "wand", "water", "whale", "wheat", "willow", "window", "winter",
+    "wren", "yacht", "yarn", "zebra", "zephyr",
+];
+
+/// The fewest words a verification phrase can have. Shorter phrases are too easy to
+/// guess or to find in a description by chance, and an empty one matches anything.
+pub const MIN_PHRASE_WORDS: usize = 4;
+
+/// Generates a phrase of `words` random words, separated by spaces (i.e. `otter maple
+/// lantern comet quartz tulip`). Each word adds 8 bits of randomness.
+///
+/// # Errors
+///
+/// This function returns an error if:
+/// - `words` is less than [`MIN_PHRASE_WORDS`]
+/// - The operating system's random number generator fails
+pub fn generate_phrase(words: usize) -> Result<String, ClientError> {
+    if words < MIN_PHRASE_WORDS {
+        return Err(ClientError::PhraseTooShort(words));
+    }
+    let mut bytes = vec![0_u8; words];
+    getrandom::getrandom(&mut bytes).map_err(|e| ClientError::RandomnessError(e.to_string()))?;
+    Ok(bytes
+        .into_iter()
+        .map(|byte| WORDS[usize::from(byte)])
+        .collect::<Vec<_>>()
+        .join(" "))
+}
+
+/// The account being verified, by ID or by username.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VerificationTarget {
+    Id(usize),
+    Username(String),
+}
+
+impl VerificationTarget {
+    // Usernames are case-insensitive, so "Builderman" and "builderman" share a challenge
+    fn key(&self) -> VerificationTarget {
+        match self {
+            Self::Id(id) => Self::Id(*id),
+            Self::Username(name) => Self::Username(name.to_lowercase()),
+        }
+    }
+}
+
+impl From<usize> for VerificationTarget {
+    fn from(id: usize) -> Self {
+        Self::Id(id)
+    }
+}
+
+impl From<&str> for VerificationTarget {
+    fn from(username: &str) -> Self {
+        Self::Username(username.to_string())
+    }
+}
+
+impl From<String> for VerificationTarget {
+    fn from(username: String) -> Self {
+        Self::Username(username)
+    }
+}
+
+/// A phrase that was handed out and is waiting to show up in a profile description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingVerification {
+    /// The phrase that has to be in the description.
+    pub phrase: String,
+    /// When the phrase stops being accepted.
+    pub expires_at: Instant,
+}
+
+/// How a [`check`](Verifier::check) turned out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VerificationResult {
+    /// The phrase is in the account's description. The challenge is finished.
+    Verified { user_id: usize },
+    /// The phrase isn't in the account's description (yet). The challenge is kept, so
+    /// the check can be tried again.
+    Mismatch,
+    /// The challenge expired before it was completed. It has been discarded.
+    Expired,
+    /// No challenge was started for this account.
+    NotPending,
+    /// No account has this username.
+    UnknownUser,
+}
+
+/// Hands out verification phrases and checks for them. Every challenge belongs to one
+/// account; starting a new one for the same account replaces the old one.
+#[derive(Debug)]
+pub struct Verifier {
+    ttl: Duration,
+    words: usize,
+    pending: Mutex<HashMap<VerificationTarget, PendingVerification>>,
+}
+
+impl Verifier {
+    /// Creates a verifier whose phrases are good for `ttl`. Phrases are 6 words long by
+    /// default.
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            words: 6,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets how many words each phrase has. Anything under [`MIN_PHRASE_WORDS`] is raised
+    /// to it.
+    #[must_use]
+    pub fn words(mut self, words: usize) -> Self {
+        self.words = words.max(MIN_PHRASE_WORDS);
+        self
+    }
+
+    /// Starts a challenge for `target` and returns the phrase that has to go in its description.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the operating system's random number generator fails.
+    pub fn start(
+        &self,
+        target: impl Into<VerificationTarget>,
+    ) -> Result<PendingVerification, ClientError> {
+        let pending = PendingVerification {
+            phrase: generate_phrase(self.words)?,
+            expires_at: Instant::now() + self.ttl,
+        };
+        self.lock().insert(target.into().key(), pending.clone());
+        Ok(pending)
+    }
+
+    /// Returns the challenge that is waiting for `target`, if there is one that hasn't expired.
+    pub fn pending(&self, target: impl Into<VerificationTarget>) -> Option<PendingVerification> {
+        self.lock()
+            .get(&target.into().key())
+            .filter(|pending| pending.expires_at > Instant::now())
+            .cloned()
+    }
+
+    /// Discards the challenge for `target`, if there is one.
+    pub fn cancel(&self, target: impl Into<VerificationTarget>) {
+        self.lock().remove(&target.into().key());
+    }
+
+    /// Discards every expired challenge. Expired challenges are also discarded when they're
+    /// checked, so this is only needed to keep memory down when many are never checked.
+    pub fn remove_expired(&self) {
+        let now = Instant::now();
+        self.lock().retain(|_, pending| pending.expires_at > now);
+    }
+
+    /// Checks whether `target`'s live profile description contains its phrase. Usernames
+    /// are resolved to the account that currently has them. The comparison ignores case and
+    /// extra whitespace.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the user lookup or the description request fails.
+    pub async fn check(
+        &self,
+        client: &RustbloxClient,
+        target: impl Into<VerificationTarget>,
+    ) -> Result<VerificationResult, RequestError> {
+        let key = target.into().key();
+        // Copied out so the lock isn't held while the match below takes it again
+        let pending = self.lock().get(&key).cloned();
+        let phrase = match pending {
+            None => return Ok(VerificationResult::NotPending),
+            Some(pending) if pending.expires_at <= Instant::now() => {
+                self.lock().remove(&key);
+                return Ok(VerificationResult::Expired);
+            }
+            Some(pending) => pending.phrase,
+        };
+
+        let user_id = match &key {
+            VerificationTarget::Id(id) => *id,
+            VerificationTarget::Username(name) => {
+                // Lookups also match previous usernames, which don't count here
+                let user = client
+                    .get_users_from_usernames(vec![name.as_str()], false)
+                    .await?
+                    .into_iter()
+                    .find(|user| user.name.eq_ignore_ascii_case(name));
+                match user {
+                    Some(user) => user.id,
+                    None => return Ok(VerificationResult::UnknownUser),
+                }
+            }
+        };
+
+        let description = client.get_user_info(user_id).await?.description;
+        if !description_contains(&description, &phrase) {
+            return Ok(VerificationResult::Mismatch);
+        }
+
+        // It could have expired while the requests were out
+        let removed = self.lock().remove(&key);
+        match removed {
+            Some(pending) if pending.phrase == phrase && pending.expires_at > Instant::now() => {
+                Ok(VerificationResult::Verified { user_id })
+            }
+            Some(pending) if pending.phrase != phrase => {
+                // Replaced by a new challenge in the meantime, which still needs checking
+                self.lock().insert(key, pending);
+                Ok(VerificationResult::Mismatch)
+            }
+            Some(_) => Ok(VerificationResult::Expired),
+            None => Ok(VerificationResult::NotPending),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<VerificationTarget, PendingVerification>> {
+        // A panic elsewhere can't leave the map half-updated, so a poisoned lock is fine to use
+        self.pending
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Returns whether `phrase` appears in `description`, ignoring case and whitespace differences.
+pub(crate) fn description_contains(description: &str, phrase: &str) -> bool {
+    let normalize = |text: &str| {
+        text.split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let phrase = normalize(phrase);
+    // An empty phrase would match every description
+    if phrase.is_empty() {
+        return false;
+    }
+    // Padded so that "cat" doesn't match inside "concatenate"
+    format!(" {} ", normalize(description)).contains(&format!(" {phrase} "))
+}